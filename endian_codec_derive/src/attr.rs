@@ -1,7 +1,9 @@
 // handle parse of #[endian = "..."]
 
 use crate::Endian;
-use syn::{Attribute, Lit, Meta};
+use proc_macro2::{Span, TokenStream};
+use syn::spanned::Spanned;
+use syn::{Attribute, Ident, Lit, Meta, MetaList, NestedMeta, Path};
 
 pub(crate) fn endian_from_attribute(attrs: &[Attribute]) -> Option<Endian> {
     let mut endian = None;
@@ -14,18 +16,27 @@ pub(crate) fn endian_from_attribute(attrs: &[Attribute]) -> Option<Endian> {
         if let Ok(meta) = attr.parse_meta() {
             match meta {
                 Meta::Path(_) => unimplemented!(),
-                Meta::List(_) => unimplemented!(),
+                // #[endian(skip)] doesn't set the endian
+                Meta::List(ref list) if is_skip(list) => {}
+                // #[endian(le)]
+                Meta::List(list) => {
+                    assert!(endian.is_none()); // FIXME span error - only one endian can be used!
+                    let mut nested = list.nested.iter();
+                    endian = Some(match (nested.next(), nested.next()) {
+                        (Some(NestedMeta::Meta(Meta::Path(path))), None) => {
+                            match path.get_ident() {
+                                Some(ident) => endian_from_str(&ident.to_string()),
+                                None => unimplemented!(),
+                            }
+                        }
+                        _ => unimplemented!(),
+                    });
+                }
                 Meta::NameValue(nv) => {
                     assert!(nv.path.is_ident("endian"));
                     assert!(endian.is_none()); // FIXME span error - only one endian can be used!
                     endian = Some(match nv.lit {
-                        Lit::Str(v) => match v.value().as_ref() {
-                            "le" | "little" => Endian::Little,
-                            "be" | "big" => Endian::Big,
-                            "native" => unimplemented!(),
-                            "custom" => unimplemented!(),
-                            _ => unimplemented!(),
-                        },
+                        Lit::Str(v) => endian_from_str(&v.value()),
                         _ => unimplemented!(),
                     });
                 }
@@ -36,3 +47,258 @@ pub(crate) fn endian_from_attribute(attrs: &[Attribute]) -> Option<Endian> {
     }
     endian
 }
+
+// handle parse of #[endian(skip)] - field is left out of the packed representation
+
+pub(crate) fn skip_from_attribute(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path.is_ident("endian")
+            && matches!(attr.parse_meta(), Ok(Meta::List(ref list)) if is_skip(list))
+    })
+}
+
+fn is_skip(list: &MetaList) -> bool {
+    let mut nested = list.nested.iter();
+    match (nested.next(), nested.next()) {
+        (Some(NestedMeta::Meta(Meta::Path(path))), None) => path.is_ident("skip"),
+        _ => false,
+    }
+}
+
+fn endian_from_str(endian: &str) -> Endian {
+    match endian {
+        "le" | "little" => Endian::Little,
+        "be" | "big" => Endian::Big,
+        "me" | "mixed" => Endian::Mixed,
+        "pdp" | "middle" => Endian::Pdp,
+        "ne" | "native" => Endian::Native,
+        "custom" => unimplemented!(),
+        _ => unimplemented!(),
+    }
+}
+
+// handle parse of #[repr(u16)] - integer type holding discriminants of an enum
+
+pub(crate) fn repr_from_attribute(attrs: &[Attribute]) -> Option<Ident> {
+    const INTEGERS: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+    for attr in attrs {
+        if !attr.path.is_ident("repr") {
+            continue;
+        }
+        if let Ok(Meta::List(list)) = attr.parse_meta() {
+            for nested in list.nested {
+                if let NestedMeta::Meta(Meta::Path(path)) = nested {
+                    match path.get_ident() {
+                        Some(ident) if INTEGERS.contains(&ident.to_string().as_str()) => {
+                            return Some(ident.clone())
+                        }
+                        // e.g. #[repr(C)]
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+// handle parse of #[endian_codec(...)]
+
+#[derive(Default)]
+pub(crate) struct CodecAttrs {
+    // #[endian_codec(since = N)] - field is present from version N onward
+    pub since: Option<u32>,
+    // #[endian_codec(hex)] - field is written as hex ASCII digits
+    pub hex: bool,
+    // #[endian_codec(invert_bytes)] - bits of every byte of the field are reversed on the wire
+    pub invert_bytes: bool,
+    // #[endian_codec(q = N)] - float field is written as Q-format fixed-point number
+    pub q: Option<u32>,
+    // #[endian_codec(assert_field_order("a", "b"))] - expected order of struct fields
+    pub field_order: Option<(Vec<String>, Span)>,
+    // #[endian_codec(assert_packed_len = N)] - expected `PACKED_LEN` of the struct
+    pub packed_len: Option<usize>,
+    // #[endian_codec(default_on_short)] - fields missing from a short buffer are defaulted
+    pub default_on_short: bool,
+    // #[endian_codec(tag(encode = "path", decode = "path"))] - functions mapping enum to its tag
+    pub tag: Option<Tag>,
+    // #[endian_codec(compute = "expr")] - field is encoded as `expr` evaluated with `self`
+    pub compute: Option<TokenStream>,
+    // #[endian_codec(offset_from = "field")] - value is an offset from the position of `field`
+    pub offset_from: Option<(String, Span)>,
+    // #[endian_codec(align_field = N)] - field starts at an offset which is a multiple of N
+    pub align_field: Option<usize>,
+    // #[endian_codec(wire_order("b", "a"))] - order in which struct fields are encoded
+    pub wire_order: Option<Vec<String>>,
+    // #[endian_codec(checksum_range = "start..end")] - bytes hashed by `decode_le_with_checksum`
+    pub checksum_range: Option<TokenStream>,
+    // #[endian_codec(borrow)] - byte array field is borrowed by the `DecodeLEHybrid` view
+    pub borrow: bool,
+    // #[endian_codec(debug_bytes)] - `EncodeLE` derive also implements `Debug` showing the bytes
+    pub debug_bytes: bool,
+}
+
+pub(crate) struct Tag {
+    // fn(&Enum) -> T
+    pub encode: Path,
+    // fn(T) -> Option<Enum>
+    pub decode: Path,
+}
+
+pub(crate) fn codec_from_attribute(attrs: &[Attribute]) -> CodecAttrs {
+    let mut codec = CodecAttrs::default();
+    for attr in attrs {
+        if !attr.path.is_ident("endian_codec") {
+            // this is not #[endian_codec(..)] attribute
+            continue;
+        }
+
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            _ => panic!("expected #[endian_codec(...)] attribute"),
+        };
+
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("since") => {
+                    assert!(codec.since.is_none(), "`since` can be used only once");
+                    codec.since = Some(match nv.lit {
+                        Lit::Int(v) => v
+                            .base10_parse()
+                            .expect("`since` must be an unsigned integer"),
+                        _ => panic!("`since` must be an unsigned integer"),
+                    });
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("hex") => codec.hex = true,
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("invert_bytes") => {
+                    codec.invert_bytes = true
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("borrow") => {
+                    codec.borrow = true
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("debug_bytes") => {
+                    codec.debug_bytes = true
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("default_on_short") => {
+                    codec.default_on_short = true
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("q") => {
+                    assert!(codec.q.is_none(), "`q` can be used only once");
+                    codec.q = Some(match nv.lit {
+                        Lit::Int(v) => v.base10_parse().expect("`q` must be an unsigned integer"),
+                        _ => panic!("`q` must be an unsigned integer"),
+                    });
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("assert_field_order") => {
+                    let order = list
+                        .nested
+                        .iter()
+                        .map(|nested| match nested {
+                            NestedMeta::Lit(Lit::Str(name)) => name.value(),
+                            _ => panic!("`assert_field_order` expects field names as strings"),
+                        })
+                        .collect();
+                    codec.field_order = Some((order, list.span()));
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("wire_order") => {
+                    assert!(
+                        codec.wire_order.is_none(),
+                        "`wire_order` can be used only once"
+                    );
+                    let order = list
+                        .nested
+                        .iter()
+                        .map(|nested| match nested {
+                            NestedMeta::Lit(Lit::Str(name)) => name.value(),
+                            _ => panic!("`wire_order` expects field names as strings"),
+                        })
+                        .collect();
+                    codec.wire_order = Some(order);
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("offset_from") => {
+                    assert!(
+                        codec.offset_from.is_none(),
+                        "`offset_from` can be used only once"
+                    );
+                    codec.offset_from = Some(match nv.lit {
+                        Lit::Str(ref field) => (field.value(), nv.lit.span()),
+                        _ => panic!("`offset_from` expects a field name as a string"),
+                    });
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("align_field") => {
+                    assert!(
+                        codec.align_field.is_none(),
+                        "`align_field` can be used only once"
+                    );
+                    let align = match nv.lit {
+                        Lit::Int(v) => v
+                            .base10_parse()
+                            .expect("`align_field` must be an unsigned integer"),
+                        _ => panic!("`align_field` must be an unsigned integer"),
+                    };
+                    assert!(align > 0, "`align_field` must be greater than 0");
+                    codec.align_field = Some(align);
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("assert_packed_len") => {
+                    assert!(
+                        codec.packed_len.is_none(),
+                        "`assert_packed_len` can be used only once"
+                    );
+                    codec.packed_len = Some(match nv.lit {
+                        Lit::Int(v) => v
+                            .base10_parse()
+                            .expect("`assert_packed_len` must be an unsigned integer"),
+                        _ => panic!("`assert_packed_len` must be an unsigned integer"),
+                    });
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("compute") => {
+                    assert!(codec.compute.is_none(), "`compute` can be used only once");
+                    codec.compute = Some(match nv.lit {
+                        Lit::Str(expr) => expr.parse().expect("`compute` must be an expression"),
+                        _ => panic!("`compute` must be an expression in a string"),
+                    });
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("checksum_range") => {
+                    assert!(
+                        codec.checksum_range.is_none(),
+                        "`checksum_range` can be used only once"
+                    );
+                    codec.checksum_range = Some(match nv.lit {
+                        Lit::Str(range) => range.parse().expect("`checksum_range` must be a range"),
+                        _ => panic!("`checksum_range` must be a range in a string"),
+                    });
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("tag") => {
+                    assert!(codec.tag.is_none(), "`tag` can be used only once");
+                    let (mut encode, mut decode) = (None, None);
+                    for nested in list.nested {
+                        match nested {
+                            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("encode") => {
+                                encode = Some(tag_fn_path(&nv.lit))
+                            }
+                            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("decode") => {
+                                decode = Some(tag_fn_path(&nv.lit))
+                            }
+                            _ => panic!("`tag` expects `encode = \"..\"` and `decode = \"..\"`"),
+                        }
+                    }
+                    codec.tag = Some(Tag {
+                        encode: encode.expect("`tag` is missing `encode` function"),
+                        decode: decode.expect("`tag` is missing `decode` function"),
+                    });
+                }
+                _ => panic!("unknown #[endian_codec(...)] option"),
+            }
+        }
+    }
+    codec
+}
+
+fn tag_fn_path(lit: &Lit) -> Path {
+    match lit {
+        Lit::Str(path) => path.parse().expect("`tag` function must be a path"),
+        _ => panic!("`tag` function must be a path in a string"),
+    }
+}
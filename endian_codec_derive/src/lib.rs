@@ -5,12 +5,12 @@
 //! [endian_codec]:https://crates.io/crates/endian_codec
 
 extern crate proc_macro;
-use proc_macro2::TokenStream;
-use quote::{quote, quote_spanned};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::spanned::Spanned;
 use syn::{
-    parse_macro_input, parse_quote, Data, DeriveInput, Fields, GenericParam, Generics,
-    TypeParamBound,
+    parse_macro_input, parse_quote, Attribute, Data, DataEnum, DataStruct, DeriveInput, Fields,
+    GenericParam, Generics, Ident, Member, Type, TypeParamBound, Visibility,
 };
 
 mod attr;
@@ -20,6 +20,9 @@ enum Endian {
     Big,
     Little,
     Mixed,
+    Native,
+    // 32-bit words as a little-endian with swapped 16-bit halves, only set on fields
+    Pdp,
 }
 
 #[derive(Clone, Copy)]
@@ -28,104 +31,876 @@ enum Codec {
     Decode,
 }
 
-#[proc_macro_derive(PackedSize)]
+// How a decoded `#[endian_codec(compute = "...")]` field differing from its expression is reported.
+#[derive(Clone, Copy)]
+enum Mismatch {
+    Panic,
+    // `return Err(EndianError::InvalidValue)`
+    Error,
+    // push a `FieldError` into `errors`
+    Collect,
+}
+
+impl Endian {
+    // Suffix of the derive names, like `LE` in `EncodeLE`.
+    fn suffix(self) -> &'static str {
+        match self {
+            Endian::Big => "BE",
+            Endian::Little => "LE",
+            Endian::Mixed | Endian::Pdp => "ME",
+            Endian::Native => "NE",
+        }
+    }
+}
+
+impl Codec {
+    fn name(self) -> &'static str {
+        match self {
+            Codec::Encode => "Encode",
+            Codec::Decode => "Decode",
+        }
+    }
+}
+
+// Compile error for derives on unions, which don't know which of their fields is stored, and on
+// enums unless `enums` is set.
+fn unsupported_data(
+    name: &Ident,
+    data: &Data,
+    derive: &str,
+    enums: bool,
+) -> Option<proc_macro::TokenStream> {
+    let msg = match *data {
+        Data::Struct(_) => return None,
+        Data::Enum(_) if enums => return None,
+        Data::Enum(_) => format!("`{}` can be derived only for structs", derive),
+        Data::Union(_) => format!("`{}` can't be derived for unions", derive),
+    };
+    Some(syn::Error::new(name.span(), msg).to_compile_error().into())
+}
+
+#[proc_macro_derive(PackedSize, attributes(endian, endian_codec))]
 pub fn derive_endian_size(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     // Parse the input tokens into a syntax tree.
     let input = parse_macro_input!(input as DeriveInput);
 
     // Used in the quasi-quotation below as `#name`.
     let name = input.ident;
+    if let Some(err) = unsupported_data(&name, &input.data, "PackedSize", true) {
+        return err;
+    }
 
     // Add a bound `T: EncodeLE` to every type parameter T.
-    let generics = add_trait_bounds(input.generics, parse_quote!(PackedSize));
+    let generics = add_trait_bounds(input.generics, &input.data, parse_quote!(PackedSize));
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let body = bytes_size(&input.data);
+    let body = bytes_size(&input.attrs, &input.data);
+    let sensitive = endian_sensitive(&input.attrs, &input.data).map(|sensitive| {
+        quote! {
+          const ENDIAN_SENSITIVE: bool = #sensitive;
+        }
+    });
+
+    // Guard the wire layout against accidentally reordered fields.
+    if let Some((order, span)) = attr::codec_from_attribute(&input.attrs).field_order {
+        let fields: Vec<String> = match input.data {
+            Data::Struct(ref data) => data
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| match field.ident {
+                    Some(ref ident) => ident.to_string(),
+                    None => i.to_string(),
+                })
+                .collect(),
+            Data::Enum(_) | Data::Union(_) => {
+                let msg = "`assert_field_order` can be used only on structs";
+                return syn::Error::new(span, msg).to_compile_error().into();
+            }
+        };
+        if fields != order {
+            let msg = format!(
+                "fields are in order {:?}, but `assert_field_order` expects {:?}",
+                fields, order
+            );
+            return syn::Error::new(span, msg).to_compile_error().into();
+        }
+    }
+
+    // `PACKED_LEN` itself asserts the expected length, so using it with a different layout fails
+    // to compile. Concrete types are checked even if `PACKED_LEN` is never used.
+    let (body, packed_len_check) = match attr::codec_from_attribute(&input.attrs).packed_len {
+        Some(expected) => {
+            let msg = format!("`PACKED_LEN` of {} differs from `assert_packed_len`", name);
+            let body = quote! {{
+                let len = #body;
+                if len != #expected {
+                    panic!(#msg);
+                }
+                len
+            }};
+            let check = if generics.params.is_empty() {
+                quote!(const _: usize = <#name as PackedSize>::PACKED_LEN;)
+            } else {
+                quote!()
+            };
+            (body, check)
+        }
+        None => (body, quote!()),
+    };
+
+    let resolvers = match offset_resolvers(&input.vis, &input.attrs, &input.data) {
+        Ok(resolvers) => resolvers,
+        Err(err) => return err.to_compile_error().into(),
+    };
 
     let expanded = quote! {
         // The generated impl.
         impl #impl_generics PackedSize for #name #ty_generics #where_clause {
           const PACKED_LEN: usize = #body;
+          #sensitive
         }
+
+        #packed_len_check
+
+        #(
+            impl #impl_generics #name #ty_generics #where_clause {
+                #resolvers
+            }
+        )*
     };
 
     // Hand the output tokens back to the compiler.
     proc_macro::TokenStream::from(expanded)
 }
 
-fn bytes_size(data: &Data) -> TokenStream {
+// `resolve_<field>` methods for fields with `#[endian_codec(offset_from = "...")]`, returning
+// bytes of the parent buffer starting at the position of the named field plus the stored offset.
+fn offset_resolvers(
+    vis: &Visibility,
+    attrs: &[Attribute],
+    data: &Data,
+) -> syn::Result<Vec<TokenStream>> {
+    let fields = match *data {
+        Data::Struct(ref data) => wire_fields(attrs, &data.fields),
+        Data::Enum(_) => vec![],
+        Data::Union(_) => unreachable!("unions are rejected by `unsupported_data`"),
+    };
+
+    let mut resolvers = vec![];
+    for field in fields.iter() {
+        let (base, span) = match attr::codec_from_attribute(&field.attrs).offset_from {
+            Some(offset_from) => offset_from,
+            None => continue,
+        };
+        let ident = match field.ident {
+            Some(ref ident) => ident,
+            None => {
+                let msg = "`offset_from` can be used only on named fields";
+                return Err(syn::Error::new(span, msg));
+            }
+        };
+        let base_index = fields
+            .iter()
+            .position(|field| field.ident.as_ref().is_some_and(|ident| *ident == base))
+            .ok_or_else(|| syn::Error::new(span, format!("no field named `{}`", base)))?;
+        let (offsets, _) = field_offsets(&fields);
+        let base_offset = &offsets[base_index];
+        let method = format_ident!("resolve_{}", ident);
+        let doc = format!(
+            "Bytes of `buf` (the buffer `self` was decoded from) starting `{}` bytes after field `{}`. \
+             Return `None` if it points outside of `buf`.",
+            ident, base
+        );
+        resolvers.push(quote! {
+            #[doc = #doc]
+            #vis fn #method<'buf>(&self, buf: &'buf [u8]) -> Option<&'buf [u8]> {
+                let offset = <usize as core::convert::TryFrom<_>>::try_from(self.#ident).ok()?;
+                buf.get((#base_offset as usize).checked_add(offset)?..)
+            }
+        });
+    }
+    Ok(resolvers)
+}
+
+fn bytes_size(attrs: &[Attribute], data: &Data) -> TokenStream {
     match *data {
         Data::Struct(ref data) => {
-            match data.fields {
-                Fields::Named(ref fields) => {
-                    // Expands to an expression like
-                    //
-                    //     0 + <self.x as PackedSize>::PACKED_LEN + <self.y as PackedSize>::PACKED_LEN
-                    let recurse = fields.named.iter().map(|f| {
-                        let ty = &f.ty;
-                        quote_spanned! {f.span()=>
-                            <#ty as PackedSize>::PACKED_LEN
-                        }
-                    });
-
-                    quote! {
-                        0  #(+ #recurse)*
-                    }
-                }
-                Fields::Unnamed(ref fields) => {
-                    // Expands to an expression like
-                    //
-                    //     0 + <self.0 as PackedSize>::PACKED_LEN + <self.1 as PackedSize>::PACKED_LEN
-                    let recurse = fields.unnamed.iter().map(|f| {
-                        let ty = &f.ty;
-                        quote_spanned! {f.span()=>
-                            <#ty as PackedSize>::PACKED_LEN
-                        }
-                    });
-                    quote! {
-                        0 #(+ #recurse)*
-                    }
+            // Expands to an expression like
+            //
+            //     0 + <self.x as PackedSize>::PACKED_LEN + <self.y as PackedSize>::PACKED_LEN
+            //
+            // with the offset of a field rounded up if it uses `align_field`. Unit structs
+            // cannot own more than 0 bytes of heap memory.
+            let (_, end) = field_offsets(&wire_fields(attrs, &data.fields));
+            end
+        }
+        Data::Enum(ref data) => {
+            enum_variants(attrs, data);
+            match attr::codec_from_attribute(attrs).tag {
+                // size of the type returned by the encode function
+                Some(tag) => {
+                    let to_tag = tag.encode;
+                    quote!(endian_codec::tag_packed_len::<Self, _>(#to_tag))
                 }
-                Fields::Unit => {
-                    // Unit structs cannot own more than 0 bytes of heap memory.
-                    quote!(0)
+                None => {
+                    let (ty, _) = enum_wire_values(attrs, data);
+                    quote!(<#ty as PackedSize>::PACKED_LEN)
                 }
             }
         }
-        Data::Enum(_) | Data::Union(_) => unimplemented!(),
+        Data::Union(_) => unreachable!("unions are rejected by `unsupported_data`"),
     }
 }
 
-#[proc_macro_derive(EncodeLE)]
+#[proc_macro_derive(EncodeLE, attributes(endian, endian_codec))]
 pub fn derive_endian_ser_bytes(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_endian_impl(input, Endian::Little, Codec::Encode)
 }
 
-#[proc_macro_derive(EncodeBE)]
+#[proc_macro_derive(EncodeBE, attributes(endian, endian_codec))]
 pub fn derive_endian_de_bytes(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_endian_impl(input, Endian::Big, Codec::Encode)
 }
 
-#[proc_macro_derive(EncodeME, attributes(endian))]
+#[proc_macro_derive(EncodeME, attributes(endian, endian_codec))]
 pub fn derive_endian_bytes(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_endian_impl(input, Endian::Mixed, Codec::Encode)
 }
 
-#[proc_macro_derive(DecodeLE)]
+#[proc_macro_derive(DecodeLE, attributes(endian, endian_codec))]
 pub fn derive_endian_le_de_bytes(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_endian_impl(input, Endian::Little, Codec::Decode)
 }
 
-#[proc_macro_derive(DecodeBE)]
+#[proc_macro_derive(DecodeBE, attributes(endian, endian_codec))]
 pub fn derive_endian_be_de_bytes(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_endian_impl(input, Endian::Big, Codec::Decode)
 }
 
-#[proc_macro_derive(DecodeME, attributes(endian))]
+#[proc_macro_derive(DecodeME, attributes(endian, endian_codec))]
 pub fn derive_endian_me_de_bytes(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_endian_impl(input, Endian::Mixed, Codec::Decode)
 }
 
+#[proc_macro_derive(EncodeNE, attributes(endian, endian_codec))]
+pub fn derive_endian_ne_bytes(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    derive_endian_impl(input, Endian::Native, Codec::Encode)
+}
+
+#[proc_macro_derive(DecodeNE, attributes(endian, endian_codec))]
+pub fn derive_endian_ne_de_bytes(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    derive_endian_impl(input, Endian::Native, Codec::Decode)
+}
+
+#[proc_macro_derive(DecodeLEVersioned, attributes(endian, endian_codec))]
+pub fn derive_endian_le_versioned(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    // Parse the input tokens into a syntax tree.
+    let input = parse_macro_input!(input as DeriveInput);
+
+    // Used in the quasi-quotation below as `#name`.
+    let name = input.ident;
+    if let Some(err) = unsupported_data(&name, &input.data, "DecodeLEVersioned", false) {
+        return err;
+    }
+
+    // Add a bound `T: DecodeLE` to every type parameter T.
+    let generics = add_trait_bounds(input.generics, &input.data, parse_quote!(DecodeLE));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let fields = match input.data {
+        Data::Struct(ref data) => wire_fields(&input.attrs, &data.fields),
+        Data::Enum(_) | Data::Union(_) => unreachable!("only structs pass `unsupported_data`"),
+    };
+
+    let mut recurse = vec![];
+    let mut since_fields = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        let member = match field.ident {
+            Some(ref ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(i.into()),
+        };
+        let struct_size = field_packed_len(field);
+        let decode_le = decode_field(field, Endian::Little, quote!(&bytes[offset..end]));
+        let align = match attr::codec_from_attribute(&field.attrs).align_field {
+            Some(align) => quote!(offset = usize::next_multiple_of(offset, #align);),
+            None => quote!(),
+        };
+        let decode = quote_spanned! {field.span()=>
+            {
+                #align
+                let end = endian_codec::field_end(offset, #struct_size);
+                let value = #decode_le;
+                offset = end;
+                value
+            }
+        };
+        match attr::codec_from_attribute(&field.attrs).since {
+            // Fields introduced later are missing in older layouts.
+            Some(since) => {
+                let field_name = match field.ident {
+                    Some(ref ident) => ident.to_string(),
+                    None => i.to_string(),
+                };
+                since_fields.push((since, field_name));
+                recurse.push(quote_spanned! {field.span()=>
+                    #member: if version >= #since { #decode } else { Default::default() },
+                });
+            }
+            None => recurse.push(quote! { #member: #decode, }),
+        }
+    }
+
+    // For every distinct `since` version list the fields that are defaulted below it, starting
+    // from the newest one.
+    let mut versions: Vec<u32> = since_fields.iter().map(|(since, _)| *since).collect();
+    versions.sort_unstable();
+    versions.dedup();
+    let mut defaulted = quote! { &[] };
+    for version in versions.into_iter().rev() {
+        let names = since_fields
+            .iter()
+            .filter(|(since, _)| *since >= version)
+            .map(|(_, name)| name);
+        defaulted = quote! {
+            if version >= #version { #defaulted } else { &[#(#names),*] }
+        };
+    }
+
+    let expanded = quote! {
+        impl #impl_generics DecodeLEVersioned for #name #ty_generics #where_clause {
+            #[allow(unused_variables, unused_mut)]
+            fn decode_le_versioned(bytes: &[u8], version: u32) -> (Self, DecodedMeta) {
+                let mut offset = 0;
+                let value = Self { #(#recurse)* };
+                assert_eq!(offset, bytes.len());
+                (value, DecodedMeta { defaulted: #defaulted })
+            }
+        }
+    };
+
+    // Hand the output tokens back to the compiler.
+    proc_macro::TokenStream::from(expanded)
+}
+
+#[proc_macro_derive(DecodeWithEndian, attributes(endian, endian_codec))]
+pub fn derive_decode_with_endian(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    // Parse the input tokens into a syntax tree.
+    let input = parse_macro_input!(input as DeriveInput);
+
+    // Used in the quasi-quotation below as `#name`.
+    let name = input.ident;
+    if let Some(err) = unsupported_data(&name, &input.data, "DecodeWithEndian", false) {
+        return err;
+    }
+
+    // Add a bound `T: DecodeLE + DecodeBE` to every type parameter T.
+    let generics = add_trait_bounds(input.generics, &input.data, parse_quote!(DecodeLE));
+    let generics = add_trait_bounds(generics, &input.data, parse_quote!(DecodeBE));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let fields = match input.data {
+        Data::Struct(ref data) => wire_fields(&input.attrs, &data.fields),
+        Data::Enum(_) | Data::Union(_) => unreachable!("only structs pass `unsupported_data`"),
+    };
+
+    let (offsets, _) = field_offsets(&fields);
+    let mut recurse = vec![];
+    for (i, (field, beg_offset)) in fields.iter().zip(offsets).enumerate() {
+        let member = match field.ident {
+            Some(ref ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(i.into()),
+        };
+        let struct_size = field_packed_len(field);
+        let end_offset = field_end(&beg_offset, &struct_size);
+        let bytes_slice = quote! { &bytes[#beg_offset..#end_offset] };
+        let decode_le = decode_field(field, Endian::Little, bytes_slice.clone());
+        let decode_be = decode_field(field, Endian::Big, bytes_slice);
+        recurse.push(quote_spanned! {field.span()=>
+            #member: match endian(#i) {
+                endian_codec::Endian::Little => #decode_le,
+                endian_codec::Endian::Big => #decode_be,
+            },
+        });
+    }
+
+    let expanded = quote! {
+        impl #impl_generics DecodeWithEndian for #name #ty_generics #where_clause {
+            #[inline]
+            fn decode_with_endian<F>(bytes: &[u8], mut endian: F) -> Self
+            where
+                F: FnMut(usize) -> endian_codec::Endian,
+            {
+                Self { #(#recurse)* }
+            }
+        }
+    };
+
+    // Hand the output tokens back to the compiler.
+    proc_macro::TokenStream::from(expanded)
+}
+
+/// Generate `Le<Name>` and `Be<Name>` newtypes which can be encoded and decoded only as a
+/// little- or big-endian respectively. `<Name>` has to implement both `EncodeLE`/`DecodeLE` and
+/// `EncodeBE`/`DecodeBE`.
+#[proc_macro_derive(EndianNewtypes)]
+pub fn derive_endian_newtypes(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    // Parse the input tokens into a syntax tree.
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let le_name = format_ident!("Le{}", name);
+    let be_name = format_ident!("Be{}", name);
+    let le_doc = format!("[{}] which is encoded only as a little-endian.", name);
+    let be_doc = format!("[{}] which is encoded only as a big-endian.", name);
+
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Each impl forwards to `<Name>`, so it requires the same trait from it.
+    let bounded = |bound: TokenStream| {
+        let mut generics = generics.clone();
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#name #ty_generics: #bound));
+        generics
+    };
+    let packed_generics = bounded(quote!(PackedSize));
+    let encode_le_generics = bounded(quote!(EncodeLE));
+    let decode_le_generics = bounded(quote!(DecodeLE));
+    let encode_be_generics = bounded(quote!(EncodeBE));
+    let decode_be_generics = bounded(quote!(DecodeBE));
+    let packed_where = packed_generics.split_for_impl().2;
+    let encode_le_where = encode_le_generics.split_for_impl().2;
+    let decode_le_where = decode_le_generics.split_for_impl().2;
+    let encode_be_where = encode_be_generics.split_for_impl().2;
+    let decode_be_where = decode_be_generics.split_for_impl().2;
+
+    let expanded = quote! {
+        #[doc = #le_doc]
+        #vis struct #le_name #generics (pub #name #ty_generics) #where_clause;
+
+        impl #impl_generics PackedSize for #le_name #ty_generics #packed_where {
+            const PACKED_LEN: usize = <#name #ty_generics as PackedSize>::PACKED_LEN;
+            const ENDIAN_SENSITIVE: bool = <#name #ty_generics as PackedSize>::ENDIAN_SENSITIVE;
+        }
+
+        impl #impl_generics EncodeLE for #le_name #ty_generics #encode_le_where {
+            #[inline]
+            fn encode_as_le_bytes(&self, bytes: &mut [u8]) -> usize {
+                EncodeLE::encode_as_le_bytes(&self.0, bytes)
+            }
+        }
+
+        impl #impl_generics DecodeLE for #le_name #ty_generics #decode_le_where {
+            #[inline]
+            fn decode_from_le_bytes(bytes: &[u8]) -> Self {
+                Self(DecodeLE::decode_from_le_bytes(bytes))
+            }
+        }
+
+        #[doc = #be_doc]
+        #vis struct #be_name #generics (pub #name #ty_generics) #where_clause;
+
+        impl #impl_generics PackedSize for #be_name #ty_generics #packed_where {
+            const PACKED_LEN: usize = <#name #ty_generics as PackedSize>::PACKED_LEN;
+            const ENDIAN_SENSITIVE: bool = <#name #ty_generics as PackedSize>::ENDIAN_SENSITIVE;
+        }
+
+        impl #impl_generics EncodeBE for #be_name #ty_generics #encode_be_where {
+            #[inline]
+            fn encode_as_be_bytes(&self, bytes: &mut [u8]) -> usize {
+                EncodeBE::encode_as_be_bytes(&self.0, bytes)
+            }
+        }
+
+        impl #impl_generics DecodeBE for #be_name #ty_generics #decode_be_where {
+            #[inline]
+            fn decode_from_be_bytes(bytes: &[u8]) -> Self {
+                Self(DecodeBE::decode_from_be_bytes(bytes))
+            }
+        }
+    };
+
+    // Hand the output tokens back to the compiler.
+    proc_macro::TokenStream::from(expanded)
+}
+
+/// Generate `<Name>Hybrid<'a>` decoded from little-endian bytes, which borrows byte array fields
+/// marked with `#[borrow]` (or `#[endian_codec(borrow)]`) from the buffer and owns all other
+/// fields.
+#[proc_macro_derive(DecodeLEHybrid, attributes(endian, endian_codec, borrow))]
+pub fn derive_decode_le_hybrid(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    // Parse the input tokens into a syntax tree.
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let hybrid_name = format_ident!("{}Hybrid", name);
+    let doc = format!(
+        "[{}] decoded from little-endian bytes, borrowing fields marked with `borrow`.",
+        name
+    );
+
+    if !input.generics.params.is_empty() {
+        let msg = "`DecodeLEHybrid` doesn't support generic structs, the view only adds a lifetime";
+        return syn::Error::new(input.generics.span(), msg)
+            .to_compile_error()
+            .into();
+    }
+    let data_fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(_) => &data.fields,
+            Fields::Unnamed(_) | Fields::Unit => {
+                let msg = "`DecodeLEHybrid` needs a struct with named fields";
+                return syn::Error::new(name.span(), msg).to_compile_error().into();
+            }
+        },
+        Data::Enum(_) | Data::Union(_) => {
+            let msg = "`DecodeLEHybrid` can be derived only for structs";
+            return syn::Error::new(name.span(), msg).to_compile_error().into();
+        }
+    };
+
+    let fields = wire_fields(&input.attrs, data_fields);
+    let (offsets, end) = field_offsets(&fields);
+    let mut members = vec![];
+    let mut decodes = vec![];
+    for (field, offset) in fields.iter().zip(offsets) {
+        let struct_size = field_packed_len(field);
+        let end_offset = field_end(&offset, &struct_size);
+        let bytes_slice = quote! { &bytes[#offset..#end_offset] };
+        let decode = if is_borrowed(field) {
+            let ty = &field.ty;
+            quote_spanned! {field.span()=>
+                <&'a #ty as core::convert::TryFrom<&'a [u8]>>::try_from(#bytes_slice).unwrap()
+            }
+        } else {
+            decode_field(field, Endian::Little, bytes_slice)
+        };
+        let ident = &field.ident;
+        members.push(ident);
+        decodes.push(decode);
+    }
+    let definitions = data_fields.iter().map(|field| {
+        let field_vis = &field.vis;
+        let ident = &field.ident;
+        let ty = &field.ty;
+        if is_borrowed(field) {
+            quote! { #field_vis #ident: &'a #ty }
+        } else {
+            quote! { #field_vis #ident: #ty }
+        }
+    });
+
+    let expanded = quote! {
+        #[doc = #doc]
+        #vis struct #hybrid_name<'a> {
+            #(#definitions,)*
+            _bytes: core::marker::PhantomData<&'a [u8]>,
+        }
+
+        impl<'a> #hybrid_name<'a> {
+            /// Read `bytes` slice packed as little-endian bytes, borrowing the `borrow` fields.
+            ///
+            /// # Panics
+            ///
+            /// Panic if `bytes` doesn't hold exactly the packed size of the struct.
+            #vis fn decode_from_le_bytes(bytes: &'a [u8]) -> Self {
+                assert_eq!(#end, bytes.len());
+                Self {
+                    #(#members: #decodes,)*
+                    _bytes: core::marker::PhantomData,
+                }
+            }
+        }
+    };
+
+    // Hand the output tokens back to the compiler.
+    proc_macro::TokenStream::from(expanded)
+}
+
+// Field borrowed by the `DecodeLEHybrid` view, marked with `#[borrow]` or `#[endian_codec(borrow)]`.
+fn is_borrowed(field: &Field) -> bool {
+    attr::codec_from_attribute(&field.attrs).borrow
+        || field.attrs.iter().any(|attr| attr.path.is_ident("borrow"))
+}
+
+#[proc_macro_derive(HashLE, attributes(endian, endian_codec))]
+pub fn derive_hash_le(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    // Parse the input tokens into a syntax tree.
+    let input = parse_macro_input!(input as DeriveInput);
+
+    // Used in the quasi-quotation below as `#name`.
+    let name = input.ident;
+    if let Some(err) = unsupported_data(&name, &input.data, "HashLE", true) {
+        return err;
+    }
+
+    // Add a bound `T: HashLE` to every type parameter T.
+    let generics = add_trait_bounds(input.generics, &input.data, parse_quote!(HashLE));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let fields = match input.data {
+        Data::Struct(ref data) => wire_fields(&input.attrs, &data.fields),
+        Data::Enum(_) => vec![],
+        Data::Union(_) => unreachable!("unions are rejected by `unsupported_data`"),
+    };
+
+    let enum_hash = match input.data {
+        Data::Enum(ref data) => enum_hash_le(&input.attrs, data),
+        _ => quote!(),
+    };
+
+    let (offsets, _) = field_offsets(&fields);
+    let mut prev_end = quote!(0);
+    let recurse = fields
+        .iter()
+        .zip(offsets)
+        .enumerate()
+        .map(|(i, (field, offset))| {
+            let member = match field.ident {
+                Some(ref ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(i.into()),
+            };
+            let codec_attrs = attr::codec_from_attribute(&field.attrs);
+            // zero bytes written by `EncodeLE` before an aligned field
+            let padding = match codec_attrs.align_field {
+                Some(_) => quote! {
+                    endian_codec::hash_zeros(state, #offset - #prev_end);
+                },
+                None => quote!(),
+            };
+            prev_end = field_end(&offset, &field_packed_len(field));
+            let hash = if is_skipped(field) {
+                // not on the wire
+                quote!()
+            } else if codec_attrs.hex
+                || codec_attrs.invert_bytes
+                || codec_attrs.q.is_some()
+                || codec_attrs.compute.is_some()
+            {
+                // bytes on the wire differ from the field type, so hash what would be encoded
+                let struct_size = field_packed_len(field);
+                let encode = encode_field(
+                    field,
+                    Endian::Little,
+                    quote!(&self.#member),
+                    quote!(&mut wire),
+                );
+                quote_spanned! {field.span()=>
+                    let mut wire = [0; #struct_size];
+                    #encode;
+                    core::hash::Hasher::write(state, &wire);
+                }
+            } else {
+                quote_spanned! {field.span()=>
+                    HashLE::hash_le(&self.#member, state);
+                }
+            };
+            quote!(#padding #hash)
+        });
+
+    let expanded = quote! {
+        impl #impl_generics HashLE for #name #ty_generics #where_clause {
+            #[inline]
+            fn hash_le<H: core::hash::Hasher>(&self, state: &mut H) {
+                #enum_hash
+                #(#recurse)*
+            }
+        }
+    };
+
+    // Hand the output tokens back to the compiler.
+    proc_macro::TokenStream::from(expanded)
+}
+
+#[proc_macro_derive(SwapEndian, attributes(endian, endian_codec))]
+pub fn derive_swap_endian(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    // Parse the input tokens into a syntax tree.
+    let input = parse_macro_input!(input as DeriveInput);
+
+    // Used in the quasi-quotation below as `#name`.
+    let name = input.ident;
+
+    // Add a bound `T: SwapEndian` to every type parameter T.
+    let generics = add_trait_bounds(input.generics, &input.data, parse_quote!(SwapEndian));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match input.data {
+        Data::Struct(ref data) => {
+            let fields = wire_fields(&input.attrs, &data.fields);
+            let (offsets, end) = field_offsets(&fields);
+            let mut recurse = vec![];
+            for (field, offset) in fields.into_iter().zip(offsets) {
+                if is_skipped(field) {
+                    continue;
+                }
+                let codec_attrs = attr::codec_from_attribute(&field.attrs);
+                let ty = wire_type(field, &codec_attrs);
+                let size = field_packed_len(field);
+                let end_offset = field_end(&offset, &size);
+                if codec_attrs.hex {
+                    // hex digits of the bytes, which are flipped as the field type
+                    let ty = &field.ty;
+                    recurse.push(quote_spanned! {field.span()=>
+                        endian_codec::hex::swap_endian_in_buffer::<#ty>(&mut bytes[#offset..#end_offset]);
+                    });
+                    continue;
+                }
+                recurse.push(quote_spanned! {field.span()=>
+                    <#ty as SwapEndian>::swap_endian_in_buffer(&mut bytes[#offset..#end_offset]);
+                });
+            }
+            quote! {
+                #(#recurse)*
+                debug_assert_eq!(#end, bytes.len());
+            }
+        }
+        // variant index or tag is a single value
+        Data::Enum(ref data) => match attr::codec_from_attribute(&input.attrs).tag {
+            Some(tag) => {
+                enum_variants(&input.attrs, data);
+                let to_tag = tag.encode;
+                quote! {
+                    endian_codec::swap_tag_endian_in_buffer::<Self, _>(#to_tag, bytes)
+                }
+            }
+            None => {
+                let (ty, _) = enum_wire_values(&input.attrs, data);
+                quote!(<#ty as SwapEndian>::swap_endian_in_buffer(bytes))
+            }
+        },
+        Data::Union(_) => {
+            let msg = "`SwapEndian` can't be derived for unions";
+            return syn::Error::new(name.span(), msg).to_compile_error().into();
+        }
+    };
+
+    let expanded = quote! {
+        impl #impl_generics SwapEndian for #name #ty_generics #where_clause {
+            #[inline]
+            fn swap_endian_in_buffer(bytes: &mut [u8]) {
+                #body
+            }
+        }
+    };
+
+    // Hand the output tokens back to the compiler.
+    proc_macro::TokenStream::from(expanded)
+}
+
+/// Implement `serde::Serialize` and `serde::Deserialize` storing the type as its little-endian
+/// bytes. Needs the `serde` feature of `endian_codec` and `EncodeLE` / `DecodeLE` implemented.
+#[proc_macro_derive(SerdeLE)]
+pub fn derive_serde_le(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    // Parse the input tokens into a syntax tree.
+    let input = parse_macro_input!(input as DeriveInput);
+
+    // Used in the quasi-quotation below as `#name`.
+    let name = input.ident;
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut de_generics = input.generics.clone();
+    de_generics.params.insert(0, parse_quote!('de));
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics endian_codec::serde_impl::serde::Serialize for #name #ty_generics #where_clause {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: endian_codec::serde_impl::serde::Serializer,
+            {
+                endian_codec::serde_impl::serialize_le(self, serializer)
+            }
+        }
+
+        impl #de_impl_generics endian_codec::serde_impl::serde::Deserialize<'de> for #name #ty_generics #where_clause {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: endian_codec::serde_impl::serde::Deserializer<'de>,
+            {
+                endian_codec::serde_impl::deserialize_le(deserializer)
+            }
+        }
+    };
+
+    // Hand the output tokens back to the compiler.
+    proc_macro::TokenStream::from(expanded)
+}
+
+#[proc_macro_derive(DebugLayout, attributes(endian, endian_codec))]
+pub fn derive_debug_layout(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    // Parse the input tokens into a syntax tree.
+    let input = parse_macro_input!(input as DeriveInput);
+
+    // Used in the quasi-quotation below as `#name`.
+    let name = input.ident;
+    if let Some(err) = unsupported_data(&name, &input.data, "DebugLayout", false) {
+        return err;
+    }
+
+    // Add a bound `T: PackedSize` to every type parameter T.
+    let generics = add_trait_bounds(input.generics, &input.data, parse_quote!(PackedSize));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let fields = match input.data {
+        Data::Struct(ref data) => wire_fields(&input.attrs, &data.fields),
+        Data::Enum(_) | Data::Union(_) => unreachable!("only structs pass `unsupported_data`"),
+    };
+
+    let default_endian = attr::endian_from_attribute(&input.attrs);
+    let (offsets, _) = field_offsets(&fields);
+    let mut recurse = vec![];
+    let mut schema = vec![];
+    for (i, (field, offset)) in fields.iter().zip(offsets).enumerate() {
+        if is_skipped(field) {
+            continue;
+        }
+        let field_name = match field.ident {
+            Some(ref ident) => ident.to_string(),
+            None => i.to_string(),
+        };
+        let size = field_packed_len(field);
+        let field_endian = attr::endian_from_attribute(&field.attrs).or(default_endian);
+        let endian = match field_endian {
+            Some(Endian::Little) => quote!(Some(endian_codec::Endian::Little)),
+            Some(Endian::Big) => quote!(Some(endian_codec::Endian::Big)),
+            Some(Endian::Mixed) | Some(Endian::Native) | Some(Endian::Pdp) | None => quote!(None),
+        };
+        let endian_name = match field_endian {
+            Some(Endian::Little) => "le",
+            Some(Endian::Big) => "be",
+            Some(Endian::Native) => "ne",
+            Some(Endian::Pdp) => "pdp",
+            Some(Endian::Mixed) | None => "me",
+        };
+        schema.push(quote_spanned! {field.span()=>
+            (#field_name, #offset, #size, #endian_name)
+        });
+        recurse.push(quote_spanned! {field.span()=>
+            endian_codec::FieldLayout {
+                name: #field_name,
+                offset: #offset,
+                size: #size,
+                endian: #endian,
+            }
+        });
+    }
+
+    let expanded = quote! {
+        impl #impl_generics DebugLayout for #name #ty_generics #where_clause {
+            const FIELDS: &'static [endian_codec::FieldLayout] = &[#(#recurse),*];
+            const SCHEMA: &'static [(&'static str, usize, usize, &'static str)] = &[#(#schema),*];
+        }
+    };
+
+    // Hand the output tokens back to the compiler.
+    proc_macro::TokenStream::from(expanded)
+}
+
 fn derive_endian_impl(
     input: proc_macro::TokenStream,
     endian: Endian,
@@ -136,25 +911,130 @@ fn derive_endian_impl(
 
     // Used in the quasi-quotation below as `#name`.
     let name = input.ident;
+    let derive = format!("{}{}", codec.name(), endian.suffix());
+    if let Some(err) = unsupported_data(&name, &input.data, &derive, true) {
+        return err;
+    }
+
+    // Add a bound `T: (Big/Little/Mixed)Endian(Encode/Decode)` to every type parameter T.
+    let generics = match codec {
+        Codec::Encode => match endian {
+            Endian::Little => add_trait_bounds(input.generics, &input.data, parse_quote!(EncodeLE)),
+            Endian::Big => add_trait_bounds(input.generics, &input.data, parse_quote!(EncodeBE)),
+            Endian::Mixed | Endian::Pdp => {
+                add_trait_bounds(input.generics, &input.data, parse_quote!(EncodeME))
+            }
+            Endian::Native => add_trait_bounds(input.generics, &input.data, parse_quote!(EncodeNE)),
+        },
+        Codec::Decode => match endian {
+            Endian::Little => add_trait_bounds(input.generics, &input.data, parse_quote!(DecodeLE)),
+            Endian::Big => add_trait_bounds(input.generics, &input.data, parse_quote!(DecodeBE)),
+            Endian::Mixed | Endian::Pdp => {
+                add_trait_bounds(input.generics, &input.data, parse_quote!(DecodeME))
+            }
+            Endian::Native => add_trait_bounds(input.generics, &input.data, parse_quote!(DecodeNE)),
+        },
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Generate an expression to sum up the heap size of each field.
+    let body = codec_data_expands(&name, &input.attrs, &input.data, endian, codec);
+
+    // Sizes of generic fields aren't known until the type is used, so only check concrete structs.
+    let guards = match endian {
+        Endian::Mixed if generics.type_params().next().is_none() => {
+            field_endian_guards(&input.data)
+        }
+        _ => quote!(),
+    };
+
+    // Check decoded `#[endian_codec(compute = "...")]` fields against their expressions.
+    let body = match (codec, &input.data) {
+        (Codec::Decode, Data::Struct(data)) => {
+            computed_checks(&name, &generics, &data.fields, body, Mismatch::Panic)
+        }
+        _ => body,
+    };
 
-    // Add a bound `T: (Big/Little/Mixed)Endian(Encode/Decode)` to every type parameter T.
-    let generics = match codec {
-        Codec::Encode => match endian {
-            Endian::Little => add_trait_bounds(input.generics, parse_quote!(EncodeLE)),
-            Endian::Big => add_trait_bounds(input.generics, parse_quote!(EncodeBE)),
-            Endian::Mixed => add_trait_bounds(input.generics, parse_quote!(EncodeME)),
-        },
-        Codec::Decode => match endian {
-            Endian::Little => add_trait_bounds(input.generics, parse_quote!(DecodeLE)),
-            Endian::Big => add_trait_bounds(input.generics, parse_quote!(DecodeBE)),
-            Endian::Mixed => add_trait_bounds(input.generics, parse_quote!(DecodeME)),
+    // Decoding from an array of the exact size can't fail. Array lengths can't depend on
+    // generic parameters, so it's only provided for concrete types.
+    let from_array = match (codec, endian) {
+        (Codec::Decode, Endian::Little) if generics.params.is_empty() => quote! {
+            impl From<&[u8; <#name as PackedSize>::PACKED_LEN]> for #name {
+                #[inline]
+                fn from(bytes: &[u8; <#name as PackedSize>::PACKED_LEN]) -> Self {
+                    DecodeLE::decode_from_le_bytes(bytes)
+                }
+            }
         },
+        _ => quote!(),
     };
 
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let collect_errors = match (codec, endian, &input.data) {
+        (Codec::Decode, Endian::Little, Data::Struct(data)) => {
+            collect_errors_method(&name, &generics, &input.attrs, &data.fields)
+        }
+        _ => quote!(),
+    };
 
-    // Generate an expression to sum up the heap size of each field.
-    let body = codec_data_expands(&input.data, endian, codec);
+    // Only a part of the packed bytes is covered by the checksum.
+    let checksum = match (
+        codec,
+        endian,
+        attr::codec_from_attribute(&input.attrs).checksum_range,
+    ) {
+        (Codec::Decode, Endian::Little, Some(range)) => quote! {
+            fn decode_le_with_checksum<H: core::hash::Hasher>(bytes: &[u8], mut hasher: H) -> (Self, u64) {
+                let value = DecodeLE::decode_from_le_bytes(bytes);
+                hasher.write(&bytes[#range]);
+                (value, hasher.finish())
+            }
+        },
+        _ => quote!(),
+    };
+
+    // Enums can hold an unknown value, which `try_decode_from_*_bytes` reports as an error.
+    // Structs pass up errors of their fields.
+    let try_decode = match (codec, &input.data) {
+        (Codec::Decode, Data::Enum(_)) | (Codec::Decode, Data::Struct(_)) => {
+            let method = match endian {
+                Endian::Little => quote!(try_decode_from_le_bytes),
+                Endian::Big => quote!(try_decode_from_be_bytes),
+                Endian::Mixed | Endian::Pdp => quote!(try_decode_from_me_bytes),
+                Endian::Native => quote!(try_decode_from_ne_bytes),
+            };
+            let body = match input.data {
+                Data::Enum(ref data) => enum_try_decode(&input.attrs, data, endian),
+                Data::Struct(ref data) => {
+                    let body = struct_try_decode(&input.attrs, data, endian);
+                    let body =
+                        computed_checks(&name, &generics, &data.fields, body, Mismatch::Error);
+                    quote!(Ok({ #body }))
+                }
+                Data::Union(_) => unreachable!("unions are rejected by `unsupported_data`"),
+            };
+            let check_len = if attr::codec_from_attribute(&input.attrs).default_on_short {
+                quote! {
+                    if bytes.len() > <Self as PackedSize>::PACKED_LEN {
+                        endian_codec::check_decode_len(bytes.len(), <Self as PackedSize>::PACKED_LEN)?;
+                    }
+                }
+            } else {
+                quote! {
+                    endian_codec::check_decode_len(bytes.len(), <Self as PackedSize>::PACKED_LEN)?;
+                }
+            };
+            quote! {
+                #[inline]
+                fn #method(bytes: &[u8]) -> Result<Self, endian_codec::EndianError> {
+                    #check_len
+                    #body
+                }
+            }
+        }
+        _ => quote!(),
+    };
 
     // The generated impl.
     let expanded = match codec {
@@ -162,7 +1042,7 @@ fn derive_endian_impl(
             Endian::Little => quote! {
                 impl #impl_generics EncodeLE for #name #ty_generics #where_clause {
                      #[inline]
-                     fn encode_as_le_bytes(&self, bytes: &mut [u8]) {
+                     fn encode_as_le_bytes(&self, bytes: &mut [u8]) -> usize {
                        #body
                      }
                 }
@@ -170,15 +1050,23 @@ fn derive_endian_impl(
             Endian::Big => quote! {
                 impl #impl_generics EncodeBE for #name #ty_generics #where_clause {
                      #[inline]
-                     fn encode_as_be_bytes(&self, bytes: &mut [u8]) {
+                     fn encode_as_be_bytes(&self, bytes: &mut [u8]) -> usize {
                        #body
                      }
                 }
             },
-            Endian::Mixed => quote! {
+            Endian::Mixed | Endian::Pdp => quote! {
                 impl #impl_generics EncodeME for #name #ty_generics #where_clause {
                      #[inline]
-                     fn encode_as_me_bytes(&self, bytes: &mut [u8]) {
+                     fn encode_as_me_bytes(&self, bytes: &mut [u8]) -> usize {
+                       #body
+                     }
+                }
+            },
+            Endian::Native => quote! {
+                impl #impl_generics EncodeNE for #name #ty_generics #where_clause {
+                     #[inline]
+                     fn encode_as_ne_bytes(&self, bytes: &mut [u8]) -> usize {
                        #body
                      }
                 }
@@ -189,132 +1077,998 @@ fn derive_endian_impl(
                 impl #impl_generics DecodeLE for #name #ty_generics #where_clause {
                      #[inline]
                      fn decode_from_le_bytes(bytes: &[u8]) -> Self {
-                       Self { #body }
+                       #body
                      }
+
+                     #try_decode
+
+                     #collect_errors
+
+                     #checksum
                 }
             },
             Endian::Big => quote! {
                 impl #impl_generics DecodeBE for #name #ty_generics #where_clause {
                      #[inline]
                      fn decode_from_be_bytes(bytes: &[u8]) -> Self {
-                       Self { #body }
+                       #body
                      }
+
+                     #try_decode
                 }
             },
-            Endian::Mixed => quote! {
+            Endian::Mixed | Endian::Pdp => quote! {
                 impl #impl_generics DecodeME for #name #ty_generics #where_clause {
                      #[inline]
                      fn decode_from_me_bytes(bytes: &[u8]) -> Self {
-                       Self { #body }
+                       #body
+                     }
+
+                     #try_decode
+                }
+            },
+            Endian::Native => quote! {
+                impl #impl_generics DecodeNE for #name #ty_generics #where_clause {
+                     #[inline]
+                     fn decode_from_ne_bytes(bytes: &[u8]) -> Self {
+                       #body
                      }
+
+                     #try_decode
                 }
             },
         },
     };
 
+    let debug = match (codec, endian) {
+        (Codec::Encode, Endian::Little) if attr::codec_from_attribute(&input.attrs).debug_bytes => {
+            debug_bytes_impl(&name, &generics, &input.data)
+        }
+        _ => quote!(),
+    };
+
+    let expanded = quote! {
+        #expanded
+        #from_array
+        #guards
+        #debug
+    };
+
     // Hand the output tokens back to the compiler.
     proc_macro::TokenStream::from(expanded)
 }
 
-use syn::{punctuated::Punctuated, token::Comma, Field};
+// Fail to compile if `#[endian = "..."]` decorates a field which takes no bytes, as it's most
+// likely a copy-paste mistake, or if `#[endian = "pdp"]` decorates a field which isn't a 32-bit
+// word.
+fn field_endian_guards(data: &Data) -> TokenStream {
+    let fields = match *data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => fields.named.iter().collect(),
+            Fields::Unnamed(ref fields) => fields.unnamed.iter().collect(),
+            Fields::Unit => vec![],
+        },
+        Data::Enum(_) => vec![],
+        Data::Union(_) => unreachable!("unions are rejected by `unsupported_data`"),
+    };
 
-fn codec_fields(fields: &Punctuated<Field, Comma>, endian: Endian, codec: Codec) -> TokenStream {
-    let mut beg_offset = quote! { 0 };
-    let mut recurse = vec![];
-    for field in fields.iter() {
-        let name = &field.ident;
+    let recurse = fields.into_iter().filter_map(|field| {
         let ty = &field.ty;
-        let struct_size = quote! { <#ty as PackedSize>::PACKED_LEN };
-        let end_offset = quote! { #beg_offset + #struct_size };
+        match attr::endian_from_attribute(&field.attrs)? {
+            Endian::Pdp => Some(quote_spanned! {field.span()=>
+                const _: () = assert!(
+                    <#ty as PackedSize>::PACKED_LEN == 4,
+                    "#[endian = \"pdp\"] attribute used on a field which doesn't take 4 bytes"
+                );
+            }),
+            _ => Some(quote_spanned! {field.span()=>
+                const _: () = assert!(
+                    <#ty as PackedSize>::PACKED_LEN != 0,
+                    "#[endian] attribute used on a field with zero size"
+                );
+            }),
+        }
+    });
+
+    quote! {
+        #(#recurse)*
+    }
+}
+
+use syn::Field;
+
+fn codec_fields(
+    fields: &[&Field],
+    endian: Endian,
+    default_endian: Endian,
+    default_on_short: bool,
+    codec: Codec,
+) -> TokenStream {
+    let (offsets, end) = field_offsets(fields);
+    let mut prev_end = quote! { 0 };
+    let mut recurse = vec![];
+    for (i, (field, beg_offset)) in fields.iter().zip(offsets).enumerate() {
+        let member = match field.ident {
+            Some(ref ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(i.into()),
+        };
+        let struct_size = field_packed_len(field);
+        let end_offset = field_end(&beg_offset, &struct_size);
         let bytes_slice = quote! { bytes[#beg_offset..#end_offset] };
+        let field_endian = match endian {
+            Endian::Mixed => match attr::endian_from_attribute(&field.attrs) {
+                // nested mixed-endian struct uses its own layout
+                Some(field_endian) => field_endian,
+                None => default_endian,
+            },
+            endian => endian,
+        };
         match codec {
-            Codec::Encode => match endian {
-                Endian::Little => recurse.push(quote_spanned! {field.span()=>
-                    debug_assert_eq!(#struct_size, #bytes_slice.len());
-                    EncodeLE::encode_as_le_bytes(&self.#name, &mut #bytes_slice);
-                }),
-                Endian::Big => recurse.push(quote_spanned! {field.span()=>
+            // nothing to write
+            Codec::Encode if is_skipped(field) => {}
+            Codec::Encode => {
+                let encode = encode_field(
+                    field,
+                    field_endian,
+                    quote!(&self.#member),
+                    quote!(&mut #bytes_slice),
+                );
+                if attr::codec_from_attribute(&field.attrs)
+                    .align_field
+                    .is_some()
+                {
+                    // leading alignment padding
+                    recurse.push(quote! {
+                        bytes[#prev_end..#beg_offset].fill(0);
+                    })
+                }
+                recurse.push(quote_spanned! {field.span()=>
                     debug_assert_eq!(#struct_size, #bytes_slice.len());
-                    EncodeBE::encode_as_be_bytes(&self.#name, &mut #bytes_slice);
-                }),
-                Endian::Mixed => {
-                    let filed_endian = attr::endian_from_attribute(&field.attrs);
-
-                    let r = match filed_endian {
-                        Some(Endian::Little) => quote_spanned! {field.span()=>
-                            debug_assert_eq!(#struct_size, #bytes_slice.len());
-                            EncodeLE::encode_as_le_bytes(&self.#name, &mut #bytes_slice);
-                        },
-                        Some(Endian::Big) => quote_spanned! {field.span()=>
-                            debug_assert_eq!(#struct_size, #bytes_slice.len());
-                            EncodeBE::encode_as_be_bytes(&self.#name, &mut #bytes_slice);
-                        },
-                        Some(Endian::Mixed) => unimplemented!(),
-                        None => quote_spanned! {field.span()=>
-                          debug_assert_eq!(#struct_size, #bytes_slice.len());
-                          EncodeME::encode_as_me_bytes(&self.#name, &mut #bytes_slice);
-                        },
-                    };
-                    recurse.push(r)
+                    #encode;
+                })
+            }
+            Codec::Decode => {
+                let mut decode = decode_field(field, field_endian, quote!(& #bytes_slice));
+                if default_on_short {
+                    decode = default_if_short(decode, &end_offset);
                 }
-            },
-            Codec::Decode => match endian {
-                Endian::Little => recurse.push(quote_spanned! {field.span()=>
-                    #name: DecodeLE::decode_from_le_bytes(& #bytes_slice),
-                }),
-                Endian::Big => recurse.push(quote_spanned! {field.span()=>
-                    #name: DecodeBE::decode_from_be_bytes(& #bytes_slice),
-                }),
-                Endian::Mixed => {
-                    let filed_endian = attr::endian_from_attribute(&field.attrs);
-
-                    let r = match filed_endian {
-                        Some(Endian::Little) => quote_spanned! {field.span()=>
-                            #name: DecodeLE::decode_from_le_bytes(& #bytes_slice),
-                        },
-                        Some(Endian::Big) => quote_spanned! {field.span()=>
-                            #name: DecodeBE::decode_from_be_bytes(& #bytes_slice),
-                        },
-                        Some(Endian::Mixed) => unimplemented!(),
-                        None => quote_spanned! {field.span()=>
-                          #name: DecodeME::decode_from_me_bytes(& #bytes_slice),
-                        },
-                    };
-                    recurse.push(r)
+                recurse.push(quote_spanned! {field.span()=>
+                    #member: #decode,
+                })
+            }
+        }
+        prev_end = end_offset;
+    }
+
+    match codec {
+        // return number of written bytes
+        Codec::Encode => quote! {
+            #(#recurse)*
+            #end
+        },
+        Codec::Decode => quote! {
+            #(#recurse)*
+        },
+    }
+}
+
+// Decode a field with `decode`, or default it if `bytes` end before `end_offset`.
+fn default_if_short(decode: TokenStream, end_offset: &TokenStream) -> TokenStream {
+    quote! {
+        if bytes.len() >= #end_offset {
+            #decode
+        } else {
+            Default::default()
+        }
+    }
+}
+
+// Fields of a struct in the order they are encoded. It's the declaration order unless the
+// struct has `#[endian_codec(wire_order("b", "a", ...))]`.
+fn wire_fields<'a>(attrs: &[Attribute], fields: &'a Fields) -> Vec<&'a Field> {
+    let fields: Vec<_> = fields.iter().collect();
+    let order = match attr::codec_from_attribute(attrs).wire_order {
+        Some(order) => order,
+        None => return fields,
+    };
+    let mut names = order.clone();
+    names.sort_unstable();
+    names.dedup();
+    assert!(
+        names.len() == order.len() && order.len() == fields.len(),
+        "`wire_order` must list every field exactly once"
+    );
+    order
+        .iter()
+        .map(|name| {
+            *fields
+                .iter()
+                .find(|field| field.ident.as_ref().is_some_and(|ident| ident == name))
+                .unwrap_or_else(|| panic!("`wire_order` lists unknown field `{}`", name))
+        })
+        .collect()
+}
+
+// Member accessing `field`, one of `fields`, which are numbered by their declaration order.
+fn field_member(fields: &Fields, field: &Field) -> Member {
+    match field.ident {
+        Some(ref ident) => Member::Named(ident.clone()),
+        None => {
+            let index = fields.iter().position(|f| core::ptr::eq(f, field));
+            Member::Unnamed(index.expect("field of the struct").into())
+        }
+    }
+}
+
+// Offsets at which `fields` start in the packed representation and the offset where the last
+// one ends. A field with `#[endian_codec(align_field = N)]` is moved to the next multiple of N.
+fn field_offsets(fields: &[&Field]) -> (Vec<TokenStream>, TokenStream) {
+    let mut end = quote!(0);
+    let mut offsets = vec![];
+    for field in fields {
+        let offset = match attr::codec_from_attribute(&field.attrs).align_field {
+            Some(align) => quote!(usize::next_multiple_of(#end, #align)),
+            None => end,
+        };
+        let size = field_packed_len(field);
+        end = field_end(&offset, &size);
+        offsets.push(offset);
+    }
+    (offsets, end)
+}
+
+// Whether any field changes its bytes with the byte order. `None` keeps the trait default.
+fn endian_sensitive(attrs: &[Attribute], data: &Data) -> Option<TokenStream> {
+    match *data {
+        Data::Struct(ref data) => {
+            let fields = data.fields.iter().filter_map(|field| {
+                let codec_attrs = attr::codec_from_attribute(&field.attrs);
+                if codec_attrs.hex || is_skipped(field) {
+                    // hex digits are written in byte order, skipped fields aren't written
+                    return None;
                 }
-            },
+                let ty = wire_type(field, &codec_attrs);
+                Some(quote!(<#ty as PackedSize>::ENDIAN_SENSITIVE))
+            });
+            Some(quote!(false #(|| #fields)*))
+        }
+        Data::Enum(ref data) => match attr::codec_from_attribute(attrs).tag {
+            Some(_) => None,
+            None => {
+                let (ty, _) = enum_wire_values(attrs, data);
+                Some(quote!(<#ty as PackedSize>::ENDIAN_SENSITIVE))
+            }
+        },
+        Data::Union(_) => unreachable!("unions are rejected by `unsupported_data`"),
+    }
+}
+
+// Fields marked with `#[endian(skip)]` and `PhantomData` markers take no bytes. Nothing is
+// encoded for them and decoding sets them to `Default::default()`.
+fn is_skipped(field: &Field) -> bool {
+    attr::skip_from_attribute(&field.attrs) || is_phantom_data(&field.ty)
+}
+
+fn is_phantom_data(ty: &Type) -> bool {
+    match *ty {
+        Type::Path(ref path) => {
+            path.qself.is_none()
+                && path
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "PhantomData")
+        }
+        _ => false,
+    }
+}
+
+// Offset where a field of `size` bytes starting at `offset` ends. The helper is a `const fn`, so
+// the expression still folds to a constant, but it panics instead of overflowing `usize`.
+fn field_end(offset: &TokenStream, size: &TokenStream) -> TokenStream {
+    quote!(endian_codec::field_end(#offset, #size))
+}
+
+// Number of bytes `field` takes in the packed representation.
+fn field_packed_len(field: &Field) -> TokenStream {
+    if is_skipped(field) {
+        return quote!(0);
+    }
+    let codec_attrs = attr::codec_from_attribute(&field.attrs);
+    let ty = wire_type(field, &codec_attrs);
+    if codec_attrs.hex {
+        // every byte is written as two hex digits
+        quote_spanned! {field.span()=> 2 * <#ty as PackedSize>::PACKED_LEN }
+    } else {
+        quote_spanned! {field.span()=> <#ty as PackedSize>::PACKED_LEN }
+    }
+}
+
+// Type which is actually encoded for `field`.
+fn wire_type(field: &Field, codec_attrs: &attr::CodecAttrs) -> TokenStream {
+    match codec_attrs.q {
+        // Q-format fixed-point number uses one sign bit and `q` fractional bits.
+        Some(q) => match q {
+            0..=7 => quote!(i8),
+            8..=15 => quote!(i16),
+            16..=31 => quote!(i32),
+            32..=63 => quote!(i64),
+            _ => panic!("`q` must be lower than 64"),
+        },
+        None => {
+            let ty = &field.ty;
+            quote!(#ty)
+        }
+    }
+}
+
+// Expression writing `value` of `field` to `bytes` using `endian` representation.
+fn encode_field(
+    field: &Field,
+    endian: Endian,
+    value: TokenStream,
+    bytes: TokenStream,
+) -> TokenStream {
+    if is_skipped(field) {
+        return quote!(0);
+    }
+    let codec_attrs = attr::codec_from_attribute(&field.attrs);
+    let ty = wire_type(field, &codec_attrs);
+    let encode = match endian {
+        Endian::Little => quote!(EncodeLE::encode_as_le_bytes),
+        Endian::Big => quote!(EncodeBE::encode_as_be_bytes),
+        Endian::Mixed => quote!(EncodeME::encode_as_me_bytes),
+        Endian::Native => quote!(EncodeNE::encode_as_ne_bytes),
+        Endian::Pdp => quote!(endian_codec::encode_as_pdp_bytes),
+    };
+    let value = match codec_attrs.compute {
+        Some(ref expr) => {
+            let field_ty = &field.ty;
+            quote_spanned! {field.span()=> &{ let computed: #field_ty = #expr; computed } }
+        }
+        None => value,
+    };
+    let value = match codec_attrs.q {
+        // Round to the nearest value (ties away from zero), `as` saturates out of range values.
+        Some(q) => quote_spanned! {field.span()=>
+            &{
+                let scaled = *#value as f64 * (1u64 << #q) as f64;
+                (if scaled < 0.0 { scaled - 0.5 } else { scaled + 0.5 }) as #ty
+            }
+        },
+        None => value,
+    };
+    // bits are reversed in the raw encoding, before it's written as hex digits
+    let invert = match codec_attrs.invert_bytes {
+        true => quote!(endian_codec::invert_bytes(&mut raw);),
+        false => quote!(),
+    };
+    if codec_attrs.hex {
+        quote_spanned! {field.span()=>
+            {
+                let mut raw = [0; <#ty as PackedSize>::PACKED_LEN];
+                #encode(#value, &mut raw);
+                #invert
+                endian_codec::hex::encode(&raw, #bytes)
+            }
+        }
+    } else if codec_attrs.invert_bytes {
+        quote_spanned! {field.span()=>
+            {
+                let raw: &mut [u8] = #bytes;
+                let len = #encode(#value, &mut *raw);
+                endian_codec::invert_bytes(raw);
+                len
+            }
+        }
+    } else {
+        quote_spanned! {field.span()=> #encode(#value, #bytes) }
+    }
+}
+
+// Expression reading `field` from `bytes` using `endian` representation.
+fn decode_field(field: &Field, endian: Endian, bytes: TokenStream) -> TokenStream {
+    decode_field_with(field, endian, bytes, false)
+}
+
+// Like `decode_field`, but the expression passes an invalid value up as an error with `?`.
+fn try_decode_field(field: &Field, endian: Endian, bytes: TokenStream) -> TokenStream {
+    decode_field_with(field, endian, bytes, true)
+}
+
+fn decode_field_with(
+    field: &Field,
+    endian: Endian,
+    bytes: TokenStream,
+    fallible: bool,
+) -> TokenStream {
+    if is_skipped(field) {
+        let ty = &field.ty;
+        return quote_spanned! {field.span()=> <#ty as Default>::default() };
+    }
+    let codec_attrs = attr::codec_from_attribute(&field.attrs);
+    let ty = wire_type(field, &codec_attrs);
+    let decode = match (endian, fallible) {
+        (Endian::Little, false) => quote!(DecodeLE::decode_from_le_bytes),
+        (Endian::Big, false) => quote!(DecodeBE::decode_from_be_bytes),
+        (Endian::Mixed, false) => quote!(DecodeME::decode_from_me_bytes),
+        (Endian::Native, false) => quote!(DecodeNE::decode_from_ne_bytes),
+        (Endian::Pdp, false) => quote!(endian_codec::decode_from_pdp_bytes),
+        (Endian::Little, true) => quote!(DecodeLE::try_decode_from_le_bytes),
+        (Endian::Big, true) => quote!(DecodeBE::try_decode_from_be_bytes),
+        (Endian::Mixed, true) => quote!(DecodeME::try_decode_from_me_bytes),
+        (Endian::Native, true) => quote!(DecodeNE::try_decode_from_ne_bytes),
+        (Endian::Pdp, true) => quote!(endian_codec::try_decode_from_pdp_bytes),
+    };
+    let (hex_decode, check) = match fallible {
+        true => (quote!(endian_codec::hex::try_decode), quote!(?)),
+        false => (quote!(endian_codec::hex::decode), quote!()),
+    };
+    let invert = match codec_attrs.invert_bytes {
+        true => quote!(endian_codec::invert_bytes(&mut raw);),
+        false => quote!(),
+    };
+    let value = if codec_attrs.hex {
+        quote_spanned! {field.span()=>
+            {
+                let mut raw = [0; <#ty as PackedSize>::PACKED_LEN];
+                #hex_decode(#bytes, &mut raw)#check;
+                #invert
+                #decode(&raw)#check
+            }
+        }
+    } else if codec_attrs.invert_bytes {
+        quote_spanned! {field.span()=>
+            {
+                let mut raw = [0; <#ty as PackedSize>::PACKED_LEN];
+                raw.copy_from_slice(#bytes);
+                #invert
+                #decode(&raw)#check
+            }
+        }
+    } else {
+        quote_spanned! {field.span()=> #decode(#bytes)#check }
+    };
+    match codec_attrs.q {
+        Some(q) => {
+            let field_ty = &field.ty;
+            quote_spanned! {field.span()=>
+                {
+                    let raw: #ty = #value;
+                    (raw as f64 / (1u64 << #q) as f64) as #field_ty
+                }
+            }
         }
-        beg_offset = quote! { #beg_offset + #struct_size }
+        None => value,
     }
+}
 
+// Wrap `body` decoding a struct to check its `#[endian_codec(compute = "...")]` fields against
+// their expressions.
+fn computed_checks(
+    name: &Ident,
+    generics: &Generics,
+    fields: &Fields,
+    body: TokenStream,
+    mismatch: Mismatch,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let computed: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .filter_map(|(i, field)| {
+            attr::codec_from_attribute(&field.attrs)
+                .compute
+                .map(|expr| (i, field, expr))
+        })
+        .collect();
+    if computed.is_empty() {
+        return body;
+    }
+    let signatures = computed.iter().map(|(i, field, _)| {
+        let method = format_ident!("compute_{}", i);
+        let ty = &field.ty;
+        quote!(fn #method(&self) -> #ty;)
+    });
+    let methods = computed.iter().map(|(i, field, expr)| {
+        let method = format_ident!("compute_{}", i);
+        let ty = &field.ty;
+        quote_spanned! {field.span()=>
+            fn #method(&self) -> #ty {
+                #expr
+            }
+        }
+    });
+    let checks = computed.iter().map(|(i, field, _)| {
+        let method = format_ident!("compute_{}", i);
+        let member = match field.ident {
+            Some(ref ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed((*i).into()),
+        };
+        let field_name = quote!(#member).to_string();
+        let report = match mismatch {
+            Mismatch::Panic => {
+                let msg = format!("`{}` doesn't match its computed value", field_name);
+                quote!(panic!(#msg))
+            }
+            Mismatch::Error => quote!(return Err(endian_codec::EndianError::InvalidValue)),
+            Mismatch::Collect => {
+                quote!(endian_codec::collect_impl::invalid_field(#field_name, &mut errors))
+            }
+        };
+        quote_spanned! {field.span()=>
+            if value.#member != Computed::#method(&value) {
+                #report;
+            }
+        }
+    });
     quote! {
-        #(#recurse)*
+        let value = { #body };
+        trait Computed {
+            #(#signatures)*
+        }
+        impl #impl_generics Computed for #name #ty_generics #where_clause {
+            #(#methods)*
+        }
+        #(#checks)*
+        value
     }
 }
 
-fn codec_data_expands(data: &Data, endian: Endian, codec: Codec) -> TokenStream {
+fn codec_data_expands(
+    name: &Ident,
+    attrs: &[Attribute],
+    data: &Data,
+    endian: Endian,
+    codec: Codec,
+) -> TokenStream {
     // this also contains `bytes` variable
+    let codec_attrs = attr::codec_from_attribute(attrs);
     match *data {
         Data::Struct(ref data) => {
-            match data.fields {
-                Fields::Named(ref fields) => codec_fields(&fields.named, endian, codec),
-                Fields::Unnamed(ref fields) => codec_fields(&fields.unnamed, endian, codec),
-                Fields::Unit => {
-                    // Unit structs cannot own more than 0 bytes of heap memory.
-                    quote!(0)
+            // `#[endian(le)]` on the struct sets the endian of fields without their own attribute.
+            let default_endian = match endian {
+                Endian::Mixed => attr::endian_from_attribute(attrs).unwrap_or(Endian::Mixed),
+                endian => endian,
+            };
+            // `#[endian_codec(default_on_short)]` defaults fields cut off from `bytes`.
+            let default_on_short = codec_attrs.default_on_short;
+            let body = match data.fields {
+                Fields::Named(_) | Fields::Unnamed(_) => codec_fields(
+                    &wire_fields(attrs, &data.fields),
+                    endian,
+                    default_endian,
+                    default_on_short,
+                    codec,
+                ),
+                Fields::Unit => match codec {
+                    // Unit structs don't take any bytes.
+                    Codec::Encode => quote!(0),
+                    Codec::Decode => quote!(),
+                },
+            };
+            match codec {
+                Codec::Encode => body,
+                // only a short buffer is accepted, a long one is still a mistake
+                Codec::Decode if default_on_short => quote! {
+                    assert!(
+                        bytes.len() <= <Self as PackedSize>::PACKED_LEN,
+                        "buffer is longer than PACKED_LEN"
+                    );
+                    Self { #body }
+                },
+                Codec::Decode => quote! { Self { #body } },
+            }
+        }
+        Data::Enum(ref data) => match codec_attrs.tag {
+            Some(ref tag) => codec_enum_tag(name, attrs, data, tag, endian, codec),
+            None => codec_enum(name, attrs, data, endian, codec),
+        },
+        Data::Union(_) => unreachable!("unions are rejected by `unsupported_data`"),
+    }
+}
+
+// Fieldless enum is stored as its variant index, or as its discriminant if it has `#[repr(..)]`.
+fn codec_enum(
+    name: &Ident,
+    attrs: &[Attribute],
+    data: &DataEnum,
+    endian: Endian,
+    codec: Codec,
+) -> TokenStream {
+    let variants = enum_variants(attrs, data);
+    let (ty, patterns) = enum_wire_values(attrs, data);
+    match codec {
+        Codec::Encode => {
+            let encode = match endian {
+                Endian::Little => quote!(EncodeLE::encode_as_le_bytes),
+                Endian::Big => quote!(EncodeBE::encode_as_be_bytes),
+                Endian::Mixed => quote!(EncodeME::encode_as_me_bytes),
+                Endian::Native => quote!(EncodeNE::encode_as_ne_bytes),
+                Endian::Pdp => quote!(endian_codec::encode_as_pdp_bytes),
+            };
+            let values = match attr::repr_from_attribute(attrs) {
+                Some(_) => variants.iter().map(|v| quote!(Self::#v as #ty)).collect(),
+                None => patterns,
+            };
+            quote! {
+                let index: #ty = match *self {
+                    #(Self::#variants => #values,)*
+                };
+                #encode(&index, bytes)
+            }
+        }
+        Codec::Decode => {
+            let decode = decode_fn(&ty, endian);
+            let invalid = match attr::repr_from_attribute(attrs) {
+                Some(_) => format!("invalid {} discriminant: {{}}", name),
+                None => format!("invalid {} variant index: {{}}", name),
+            };
+            quote! {
+                match #decode(bytes) {
+                    #(#patterns => Self::#variants,)*
+                    index => panic!(#invalid, index),
+                }
+            }
+        }
+    }
+}
+
+// `Debug` formatting fields like the standard derive, followed by the little-endian bytes as hex.
+fn debug_bytes_impl(name: &Ident, generics: &Generics, data: &Data) -> TokenStream {
+    let generics = add_trait_bounds(generics.clone(), data, parse_quote!(core::fmt::Debug));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let fields = match *data {
+        Data::Struct(ref data) => &data.fields,
+        Data::Enum(_) => {
+            let msg = "`debug_bytes` can be used only on structs";
+            return syn::Error::new(name.span(), msg).to_compile_error();
+        }
+        Data::Union(_) => unreachable!("unions are rejected by `unsupported_data`"),
+    };
+    let name_str = name.to_string();
+    let body = match *fields {
+        Fields::Named(_) => {
+            let entries = fields.iter().map(|field| {
+                let ident = &field.ident;
+                let field_name = quote!(#ident).to_string();
+                quote!(.field(#field_name, &self.#ident))
+            });
+            quote! {
+                f.debug_struct(#name_str)
+                    #(#entries)*
+                    .field("le_bytes", &format_args!("{:x}", endian_codec::hex::Encoded::le(self)))
+                    .finish()
+            }
+        }
+        Fields::Unnamed(_) | Fields::Unit => {
+            let entries = (0..fields.len()).map(|i| {
+                let member = Member::Unnamed(i.into());
+                quote!(.field(&self.#member))
+            });
+            quote! {
+                f.debug_tuple(#name_str)
+                    #(#entries)*
+                    .field(&format_args!("{:x}", endian_codec::hex::Encoded::le(self)))
+                    .finish()
+            }
+        }
+    };
+    quote! {
+        impl #impl_generics core::fmt::Debug for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                #body
+            }
+        }
+    }
+}
+
+// `decode_le_collect_errors` decoding every field on its own, so errors of all fields are
+// reported. Fields which don't map directly to their type are decoded as usual.
+fn collect_errors_method(
+    name: &Ident,
+    generics: &Generics,
+    attrs: &[Attribute],
+    struct_fields: &Fields,
+) -> TokenStream {
+    let default_on_short = attr::codec_from_attribute(attrs).default_on_short;
+    let fields = wire_fields(attrs, struct_fields);
+    let (offsets, _) = field_offsets(&fields);
+    let mut members = vec![];
+    let mut vars = vec![];
+    let mut decodes = vec![];
+    for (i, (field, offset)) in fields.iter().zip(offsets).enumerate() {
+        let (member, field_name) = match field.ident {
+            Some(ref ident) => (Member::Named(ident.clone()), ident.to_string()),
+            None => (Member::Unnamed(i.into()), i.to_string()),
+        };
+        let var = format_ident!("field_{}", i);
+        let size = field_packed_len(field);
+        let end = field_end(&offset, &size);
+        let bytes_slice = quote!(&bytes[#offset..#end]);
+        let codec_attrs = attr::codec_from_attribute(&field.attrs);
+        let mut decode = if is_skipped(field)
+            || codec_attrs.hex
+            || codec_attrs.invert_bytes
+            || codec_attrs.q.is_some()
+        {
+            let decode = try_decode_field(field, Endian::Little, bytes_slice);
+            quote! {
+                endian_codec::collect_impl::field_result(
+                    (|| Ok(#decode))(),
+                    #field_name,
+                    &mut errors,
+                )
+            }
+        } else {
+            let ty = &field.ty;
+            quote! {
+                endian_codec::collect_impl::decode_field::<#ty>(#bytes_slice, #field_name, &mut errors)
+            }
+        };
+        if default_on_short {
+            decode = quote! {
+                if bytes.len() >= #end {
+                    #decode
+                } else {
+                    Some(Default::default())
+                }
+            };
+        }
+        decodes.push(quote! { let #var = #decode; });
+        members.push(member);
+        vars.push(var);
+    }
+    let value = computed_checks(
+        name,
+        generics,
+        struct_fields,
+        quote!(Self { #(#members: #vars,)* }),
+        Mismatch::Collect,
+    );
+    let check_len = if default_on_short {
+        quote! {
+            if bytes.len() > <Self as PackedSize>::PACKED_LEN {
+                endian_codec::collect_impl::check_len(bytes.len(), <Self as PackedSize>::PACKED_LEN)?;
+            }
+        }
+    } else {
+        quote! {
+            endian_codec::collect_impl::check_len(bytes.len(), <Self as PackedSize>::PACKED_LEN)?;
+        }
+    };
+    quote! {
+        endian_codec::__if_alloc! {
+            fn decode_le_collect_errors(
+                bytes: &[u8],
+            ) -> Result<Self, endian_codec::collect_impl::Vec<endian_codec::FieldError>> {
+                #check_len
+                let mut errors = endian_codec::collect_impl::Vec::new();
+                #(#decodes)*
+                match (#(#vars,)*) {
+                    (#(Some(#vars),)*) if errors.is_empty() => {
+                        let value = { #value };
+                        if errors.is_empty() {
+                            Ok(value)
+                        } else {
+                            Err(errors)
+                        }
+                    }
+                    _ => Err(errors),
+                }
+            }
+        }
+    }
+}
+
+// Expression creating a struct in `try_decode_from_*_bytes`, passing invalid fields up with `?`.
+// With `default_on_short` fields cut off from a short `bytes` are defaulted.
+fn struct_try_decode(attrs: &[Attribute], data: &DataStruct, endian: Endian) -> TokenStream {
+    let default_on_short = attr::codec_from_attribute(attrs).default_on_short;
+    let default_endian = match endian {
+        Endian::Mixed => attr::endian_from_attribute(attrs).unwrap_or(Endian::Mixed),
+        endian => endian,
+    };
+    let fields = wire_fields(attrs, &data.fields);
+    let (offsets, _) = field_offsets(&fields);
+    let recurse = fields.iter().zip(offsets).map(|(field, offset)| {
+        let member = field_member(&data.fields, field);
+        let size = field_packed_len(field);
+        let end = field_end(&offset, &size);
+        let field_endian = match endian {
+            Endian::Mixed => attr::endian_from_attribute(&field.attrs).unwrap_or(default_endian),
+            endian => endian,
+        };
+        let mut decode = try_decode_field(field, field_endian, quote!(&bytes[#offset..#end]));
+        if default_on_short {
+            decode = default_if_short(decode, &end);
+        }
+        quote_spanned! {field.span()=> #member: #decode, }
+    });
+    quote! { Self { #(#recurse)* } }
+}
+
+// Body of `try_decode_from_*_bytes` of a fieldless enum, returning an error for an unknown value.
+fn enum_try_decode(attrs: &[Attribute], data: &DataEnum, endian: Endian) -> TokenStream {
+    let invalid = quote!(endian_codec::EndianError::InvalidValue);
+    match attr::codec_from_attribute(attrs).tag {
+        Some(tag) => {
+            let from_tag = tag.decode;
+            let decode = match endian {
+                Endian::Little => quote!(DecodeLE::decode_from_le_bytes),
+                Endian::Big => quote!(DecodeBE::decode_from_be_bytes),
+                Endian::Mixed => quote!(DecodeME::decode_from_me_bytes),
+                Endian::Native => quote!(DecodeNE::decode_from_ne_bytes),
+                Endian::Pdp => quote!(endian_codec::decode_from_pdp_bytes),
+            };
+            quote!(#from_tag(#decode(bytes)).ok_or(#invalid))
+        }
+        None => {
+            let variants = enum_variants(attrs, data);
+            let (ty, patterns) = enum_wire_values(attrs, data);
+            let decode = decode_fn(&ty, endian);
+            quote! {
+                match #decode(bytes) {
+                    #(#patterns => Ok(Self::#variants),)*
+                    _ => Err(#invalid),
+                }
+            }
+        }
+    }
+}
+
+// Function decoding a value of type `ty` stored using `endian`.
+fn decode_fn(ty: &TokenStream, endian: Endian) -> TokenStream {
+    match endian {
+        Endian::Little => quote!(<#ty as DecodeLE>::decode_from_le_bytes),
+        Endian::Big => quote!(<#ty as DecodeBE>::decode_from_be_bytes),
+        Endian::Mixed => quote!(<#ty as DecodeME>::decode_from_me_bytes),
+        Endian::Native => quote!(<#ty as DecodeNE>::decode_from_ne_bytes),
+        Endian::Pdp => quote!(endian_codec::decode_from_pdp_bytes::<#ty>),
+    }
+}
+
+// Type storing a fieldless enum and match patterns for the stored value of every variant.
+fn enum_wire_values(attrs: &[Attribute], data: &DataEnum) -> (TokenStream, Vec<TokenStream>) {
+    let variants = enum_variants(attrs, data);
+    match attr::repr_from_attribute(attrs) {
+        // discriminants aren't known to the macro, so compare with the casted variants
+        Some(repr) => {
+            let patterns = variants
+                .iter()
+                .map(|v| quote!(value if value == Self::#v as #repr))
+                .collect();
+            (quote!(#repr), patterns)
+        }
+        None => {
+            let indexes = (0..variants.len())
+                .map(|i| {
+                    let i = i as u8;
+                    quote!(#i)
+                })
+                .collect();
+            (quote!(u8), indexes)
+        }
+    }
+}
+
+// Hash the same value `EncodeLE` writes for a fieldless enum: its tag, discriminant or index.
+fn enum_hash_le(attrs: &[Attribute], data: &DataEnum) -> TokenStream {
+    let variants = enum_variants(attrs, data);
+    if let Some(tag) = attr::codec_from_attribute(attrs).tag {
+        let to_tag = &tag.encode;
+        return quote!(HashLE::hash_le(&#to_tag(self), state););
+    }
+    let (ty, patterns) = enum_wire_values(attrs, data);
+    let values = match attr::repr_from_attribute(attrs) {
+        Some(_) => variants.iter().map(|v| quote!(Self::#v as #ty)).collect(),
+        None => patterns,
+    };
+    quote! {
+        let index: #ty = match *self {
+            #(Self::#variants => #values,)*
+        };
+        HashLE::hash_le(&index, state);
+    }
+}
+
+// Fieldless enum is stored as a tag returned by user supplied functions.
+fn codec_enum_tag(
+    name: &Ident,
+    attrs: &[Attribute],
+    data: &DataEnum,
+    tag: &attr::Tag,
+    endian: Endian,
+    codec: Codec,
+) -> TokenStream {
+    enum_variants(attrs, data);
+    let (to_tag, from_tag) = (&tag.encode, &tag.decode);
+    match codec {
+        Codec::Encode => {
+            let encode = match endian {
+                Endian::Little => quote!(EncodeLE::encode_as_le_bytes),
+                Endian::Big => quote!(EncodeBE::encode_as_be_bytes),
+                Endian::Mixed => quote!(EncodeME::encode_as_me_bytes),
+                Endian::Native => quote!(EncodeNE::encode_as_ne_bytes),
+                Endian::Pdp => quote!(endian_codec::encode_as_pdp_bytes),
+            };
+            quote! {
+                #encode(&#to_tag(self), bytes)
+            }
+        }
+        Codec::Decode => {
+            let decode = match endian {
+                Endian::Little => quote!(DecodeLE::decode_from_le_bytes),
+                Endian::Big => quote!(DecodeBE::decode_from_be_bytes),
+                Endian::Mixed => quote!(DecodeME::decode_from_me_bytes),
+                Endian::Native => quote!(DecodeNE::decode_from_ne_bytes),
+                Endian::Pdp => quote!(endian_codec::decode_from_pdp_bytes),
+            };
+            let invalid = format!("invalid {} tag", name);
+            quote! {
+                match #from_tag(#decode(bytes)) {
+                    Some(value) => value,
+                    None => panic!(#invalid),
                 }
             }
         }
-        Data::Enum(_) | Data::Union(_) => unimplemented!(),
     }
 }
 
+// Variants of a fieldless enum. Without `#[repr(..)]` or a custom tag they are encoded by their
+// index, so explicit discriminants aren't allowed.
+fn enum_variants<'a>(attrs: &[Attribute], data: &'a DataEnum) -> Vec<&'a Ident> {
+    let by_index = attr::repr_from_attribute(attrs).is_none()
+        && attr::codec_from_attribute(attrs).tag.is_none();
+    if by_index {
+        assert!(
+            data.variants.len() <= 256,
+            "enum with more than 256 variants doesn't fit in u8 index"
+        );
+    }
+    data.variants
+        .iter()
+        .map(|variant| {
+            match variant.fields {
+                Fields::Unit => {}
+                _ => panic!("only enums without fields are supported"),
+            }
+            if by_index && variant.discriminant.is_some() {
+                panic!("enums with explicit discriminants need `#[repr(u8)]`, `#[repr(u16)]`, ...")
+            }
+            &variant.ident
+        })
+        .collect()
+}
+
 // Add a bound `T: trait_bound` to every type parameter T.
-fn add_trait_bounds(mut generics: Generics, trait_bound: TypeParamBound) -> Generics {
+fn add_trait_bounds(mut generics: Generics, data: &Data, trait_bound: TypeParamBound) -> Generics {
     for param in &mut generics.params {
         if let GenericParam::Type(ref mut type_param) = *param {
+            if only_in_skipped_fields(data, &type_param.ident) {
+                // e.g. type state carried by `PhantomData<T>`
+                continue;
+            }
             type_param.bounds.push(trait_bound.clone());
         }
     }
     generics
 }
+
+// Whether type parameter `param` is used by skipped fields only, so it needs no bounds.
+fn only_in_skipped_fields(data: &Data, param: &Ident) -> bool {
+    fn uses(tokens: TokenStream, param: &Ident) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(ref ident) => ident == param,
+            TokenTree::Group(ref group) => uses(group.stream(), param),
+            _ => false,
+        })
+    }
+    match *data {
+        Data::Struct(ref data) => data
+            .fields
+            .iter()
+            .all(|field| is_skipped(field) || !uses(field.ty.to_token_stream(), param)),
+        Data::Enum(_) | Data::Union(_) => false,
+    }
+}
@@ -0,0 +1,58 @@
+//! Support for `decode_le_collect_errors` generated by the `DecodeLE` derive.
+
+use crate::{check_decode_len, DecodeLE, EndianError, FieldError};
+use alloc::string::String;
+use alloc::vec;
+
+pub use alloc::vec::Vec;
+
+/// Check that the buffer length `got` is the `needed` length of the decoded type.
+pub fn check_len(got: usize, needed: usize) -> Result<(), Vec<FieldError>> {
+    check_decode_len(got, needed).map_err(|error| {
+        vec![FieldError {
+            field: String::new(),
+            error,
+        }]
+    })
+}
+
+/// Decode `field` from `bytes`, adding its errors to `errors` on failure.
+pub fn decode_field<T: DecodeLE>(
+    bytes: &[u8],
+    field: &str,
+    errors: &mut Vec<FieldError>,
+) -> Option<T> {
+    match T::decode_le_collect_errors(bytes) {
+        Ok(value) => Some(value),
+        Err(field_errors) => {
+            errors.extend(field_errors.into_iter().map(|err| err.within(field)));
+            None
+        }
+    }
+}
+
+/// Keep the decoded value of `field`, adding its error to `errors` on failure.
+pub fn field_result<T>(
+    result: Result<T, EndianError>,
+    field: &str,
+    errors: &mut Vec<FieldError>,
+) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(error) => {
+            errors.push(FieldError {
+                field: String::from(field),
+                error,
+            });
+            None
+        }
+    }
+}
+
+/// Report `field` whose decoded value doesn't match the value computed from the other fields.
+pub fn invalid_field(field: &str, errors: &mut Vec<FieldError>) {
+    errors.push(FieldError {
+        field: String::from(field),
+        error: EndianError::InvalidValue,
+    });
+}
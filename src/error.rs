@@ -0,0 +1,101 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::fmt;
+
+/// Errors reported by fallible encoding and decoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EndianError {
+    /// The buffer has fewer bytes than the encoding needs.
+    BufferTooSmall {
+        /// Number of bytes the encoding needs.
+        needed: usize,
+        /// Number of bytes available in the buffer.
+        got: usize,
+    },
+    /// The buffer has more bytes than the encoding takes.
+    BufferTooLarge {
+        /// Number of bytes the encoding takes.
+        needed: usize,
+        /// Number of bytes available in the buffer.
+        got: usize,
+    },
+    /// The [Scratch](crate::Scratch) space has fewer free slots than the decoded records need.
+    ScratchTooSmall {
+        /// Number of slots the records need.
+        needed: usize,
+        /// Number of free slots in the scratch space.
+        got: usize,
+    },
+    /// Encoding the decoded value doesn't give back the same bytes.
+    NonCanonical,
+    /// The bytes don't hold a valid value of the decoded type, e.g. a `char` out of range.
+    InvalidValue,
+}
+
+impl fmt::Display for EndianError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            EndianError::BufferTooSmall { needed, got } => {
+                write!(f, "buffer too small: needed {} bytes, got {}", needed, got)
+            }
+            EndianError::BufferTooLarge { needed, got } => {
+                write!(f, "buffer too large: needed {} bytes, got {}", needed, got)
+            }
+            EndianError::ScratchTooSmall { needed, got } => {
+                write!(f, "scratch too small: needed {} slots, got {}", needed, got)
+            }
+            EndianError::NonCanonical => f.write_str("bytes are not in canonical form"),
+            EndianError::InvalidValue => f.write_str("bytes hold an invalid value"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EndianError {}
+
+/// Lets `?` turn [EndianError](EndianError) into an I/O error of kind
+/// [InvalidData](std::io::ErrorKind::InvalidData).
+#[cfg(feature = "std")]
+impl From<EndianError> for std::io::Error {
+    fn from(err: EndianError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, err)
+    }
+}
+
+/// Error of a single field reported by
+/// [decode_le_collect_errors](crate::DecodeLE::decode_le_collect_errors).
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    /// Path of the field, e.g. `header.kind`. Empty if the whole value failed.
+    pub field: String,
+    /// What went wrong.
+    pub error: EndianError,
+}
+
+#[cfg(feature = "alloc")]
+impl FieldError {
+    // Make the error relative to the struct holding `field`.
+    pub(crate) fn within(mut self, field: &str) -> Self {
+        if !self.field.is_empty() {
+            self.field.insert(0, '.');
+        }
+        self.field.insert_str(0, field);
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.field.is_empty() {
+            self.error.fmt(f)
+        } else {
+            write!(f, "`{}`: {}", self.field, self.error)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FieldError {}
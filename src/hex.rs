@@ -0,0 +1,193 @@
+//! Hex ASCII representation used by fields marked with `#[endian_codec(hex)]` and by
+//! [Encoded](Encoded) to format encoded values.
+//!
+//! ```rust
+//! use endian_codec::{PackedSize, EncodeBE, DecodeBE};
+//!
+//! #[derive(Debug, PartialEq, Eq, PackedSize, EncodeBE, DecodeBE)]
+//! struct Device {
+//!   #[endian_codec(hex)]
+//!   id: [u8; 2],
+//! }
+//!
+//! let mut buf = [0; Device::PACKED_LEN];
+//! Device { id: [0xCA, 0xFE] }.encode_as_be_bytes(&mut buf);
+//! assert_eq!(b"cafe", &buf);
+//! ```
+
+use crate::{EncodeBE, EncodeLE, EncodeME, EndianError, PackedSize, SwapEndian};
+use core::fmt;
+
+const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Write `src` as lowercase hex ASCII digits into `dst`.
+///
+/// # Panics
+///
+/// Panic if `dst` is not exactly twice as long as `src`.
+pub fn encode(src: &[u8], dst: &mut [u8]) {
+    assert_eq!(2 * src.len(), dst.len());
+    for (byte, digits) in src.iter().zip(dst.chunks_mut(2)) {
+        digits[0] = DIGITS[usize::from(byte >> 4)];
+        digits[1] = DIGITS[usize::from(byte & 0x0F)];
+    }
+}
+
+/// Read hex ASCII digits (upper- or lowercase) from `src` into `dst`.
+///
+/// # Panics
+///
+/// Panic if `src` is not exactly twice as long as `dst` or contains a non-hex byte.
+pub fn decode(src: &[u8], dst: &mut [u8]) {
+    assert_eq!(src.len(), 2 * dst.len());
+    for (byte, digits) in dst.iter_mut().zip(src.chunks(2)) {
+        *byte = nibble(digits[0]) << 4 | nibble(digits[1]);
+    }
+}
+
+/// Like [decode](decode), but return [InvalidValue](EndianError::InvalidValue) if `src`
+/// contains a non-hex byte.
+///
+/// # Panics
+///
+/// Panic if `src` is not exactly twice as long as `dst`.
+pub fn try_decode(src: &[u8], dst: &mut [u8]) -> Result<(), EndianError> {
+    assert_eq!(src.len(), 2 * dst.len());
+    for (byte, digits) in dst.iter_mut().zip(src.chunks(2)) {
+        *byte = try_nibble(digits[0])? << 4 | try_nibble(digits[1])?;
+    }
+    Ok(())
+}
+
+/// Flip the byte order of `T` written as hex digits in `bytes`, e.g. by a field marked with
+/// `#[endian_codec(hex)]`. The digits are rewritten in lowercase.
+///
+/// ```rust
+/// let mut digits = *b"1234";
+/// endian_codec::hex::swap_endian_in_buffer::<u16>(&mut digits);
+/// assert_eq!(b"3412", &digits);
+/// ```
+///
+/// # Panics
+///
+/// Panic if `bytes` is not exactly twice as long as `T::PACKED_LEN` or contains a non-hex byte.
+pub fn swap_endian_in_buffer<T: SwapEndian + ?Sized>(bytes: &mut [u8]) {
+    let len = T::PACKED_LEN;
+    assert_eq!(2 * len, bytes.len());
+    // decode into the first half in place, digits are read before they are overwritten
+    for i in 0..len {
+        bytes[i] = nibble(bytes[2 * i]) << 4 | nibble(bytes[2 * i + 1]);
+    }
+    T::swap_endian_in_buffer(&mut bytes[..len]);
+    // encode from the end, so every byte is read before its digits overwrite it
+    for i in (0..len).rev() {
+        let byte = bytes[i];
+        bytes[2 * i] = DIGITS[usize::from(byte >> 4)];
+        bytes[2 * i + 1] = DIGITS[usize::from(byte & 0x0F)];
+    }
+}
+
+fn nibble(digit: u8) -> u8 {
+    match try_nibble(digit) {
+        Ok(nibble) => nibble,
+        Err(_) => panic!("invalid hex digit: {:#04x}", digit),
+    }
+}
+
+fn try_nibble(digit: u8) -> Result<u8, EndianError> {
+    match digit {
+        b'0'..=b'9' => Ok(digit - b'0'),
+        b'a'..=b'f' => Ok(digit - b'a' + 10),
+        b'A'..=b'F' => Ok(digit - b'A' + 10),
+        _ => Err(EndianError::InvalidValue),
+    }
+}
+
+/// Largest `PACKED_LEN` of a type which can be formatted with [Encoded](Encoded).
+pub const MAX_ENCODED_LEN: usize = 256;
+
+/// Formats the encoding of a value as hex digits with `{:x}` and `{:X}`.
+///
+/// Width, fill, zero-padding and `#` (adds `0x`) flags are honored.
+///
+/// ```rust
+/// use endian_codec::hex::Encoded;
+///
+/// assert_eq!("3412", format!("{:x}", Encoded::le(&0x1234u16)));
+/// assert_eq!("0x00001234", format!("{:#010X}", Encoded::be(&0x1234u16)));
+/// ```
+pub struct Encoded<'a, T> {
+    value: &'a T,
+    encode: fn(&T, &mut [u8]) -> usize,
+}
+
+impl<'a, T: PackedSize> Encoded<'a, T> {
+    const FITS: () = assert!(
+        T::PACKED_LEN <= MAX_ENCODED_LEN,
+        "type is too big to be formatted by Encoded"
+    );
+
+    /// Format `value` encoded as little-endian bytes.
+    pub fn le(value: &'a T) -> Self
+    where
+        T: EncodeLE,
+    {
+        let () = Self::FITS;
+        Encoded {
+            value,
+            encode: T::encode_as_le_bytes,
+        }
+    }
+
+    /// Format `value` encoded as big-endian bytes.
+    pub fn be(value: &'a T) -> Self
+    where
+        T: EncodeBE,
+    {
+        let () = Self::FITS;
+        Encoded {
+            value,
+            encode: T::encode_as_be_bytes,
+        }
+    }
+
+    /// Format `value` encoded as mixed-endian bytes.
+    pub fn me(value: &'a T) -> Self
+    where
+        T: EncodeME,
+    {
+        let () = Self::FITS;
+        Encoded {
+            value,
+            encode: T::encode_as_me_bytes,
+        }
+    }
+
+    fn fmt_hex(&self, f: &mut fmt::Formatter<'_>, upper: bool) -> fmt::Result {
+        let mut bytes = [0; MAX_ENCODED_LEN];
+        let bytes = &mut bytes[..T::PACKED_LEN];
+        (self.encode)(self.value, bytes);
+
+        let mut digits = [0; 2 * MAX_ENCODED_LEN];
+        let digits = &mut digits[..2 * T::PACKED_LEN];
+        encode(bytes, digits);
+        if upper {
+            digits.make_ascii_uppercase();
+        }
+        // hex digits are always valid UTF-8
+        let digits = core::str::from_utf8(digits).unwrap();
+        f.pad_integral(true, "0x", digits)
+    }
+}
+
+impl<'a, T: PackedSize> fmt::LowerHex for Encoded<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_hex(f, false)
+    }
+}
+
+impl<'a, T: PackedSize> fmt::UpperHex for Encoded<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_hex(f, true)
+    }
+}
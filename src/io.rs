@@ -0,0 +1,73 @@
+//! Decoding from [std::io::Read] streams, enabled with the `std` feature.
+
+use crate::{DecodeBE, DecodeLE, DecodeME};
+use std::io::{self, Read};
+use std::vec;
+
+/// Read `T` stored as a little-endian from `reader`.
+///
+/// Return the decoded value together with the number of bytes consumed from `reader`, so
+/// callers can keep track of the stream position. For types implementing
+/// [PackedSize](crate::PackedSize) it is always `T::PACKED_LEN`.
+///
+/// ```rust
+/// use endian_codec::decode_le_from_reader;
+///
+/// let mut reader: &[u8] = &[1, 0, 2, 0, 0, 0];
+/// let (a, len): (u16, usize) = decode_le_from_reader(&mut reader)?;
+/// assert_eq!((1, 2), (a, len));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Return any error of [read_exact](Read::read_exact), in particular
+/// [UnexpectedEof](io::ErrorKind::UnexpectedEof) if the stream ends before `T` is complete.
+/// Return [InvalidData](io::ErrorKind::InvalidData) wrapping the
+/// [EndianError](crate::EndianError) if the bytes read don't hold a valid `T`.
+pub fn decode_le_from_reader<T: DecodeLE, R: Read + ?Sized>(
+    reader: &mut R,
+) -> io::Result<(T, usize)> {
+    let bytes = read_packed::<R>(reader, T::PACKED_LEN)?;
+    Ok((T::try_decode_from_le_bytes(&bytes)?, bytes.len()))
+}
+
+/// Read `T` stored as a big-endian from `reader`.
+///
+/// Return the decoded value together with the number of bytes consumed from `reader`.
+///
+/// # Errors
+///
+/// Return any error of [read_exact](Read::read_exact), in particular
+/// [UnexpectedEof](io::ErrorKind::UnexpectedEof) if the stream ends before `T` is complete.
+/// Return [InvalidData](io::ErrorKind::InvalidData) wrapping the
+/// [EndianError](crate::EndianError) if the bytes read don't hold a valid `T`.
+pub fn decode_be_from_reader<T: DecodeBE, R: Read + ?Sized>(
+    reader: &mut R,
+) -> io::Result<(T, usize)> {
+    let bytes = read_packed::<R>(reader, T::PACKED_LEN)?;
+    Ok((T::try_decode_from_be_bytes(&bytes)?, bytes.len()))
+}
+
+/// Read `T` stored as a mixed-endian from `reader`.
+///
+/// Return the decoded value together with the number of bytes consumed from `reader`.
+///
+/// # Errors
+///
+/// Return any error of [read_exact](Read::read_exact), in particular
+/// [UnexpectedEof](io::ErrorKind::UnexpectedEof) if the stream ends before `T` is complete.
+/// Return [InvalidData](io::ErrorKind::InvalidData) wrapping the
+/// [EndianError](crate::EndianError) if the bytes read don't hold a valid `T`.
+pub fn decode_me_from_reader<T: DecodeME, R: Read + ?Sized>(
+    reader: &mut R,
+) -> io::Result<(T, usize)> {
+    let bytes = read_packed::<R>(reader, T::PACKED_LEN)?;
+    Ok((T::try_decode_from_me_bytes(&bytes)?, bytes.len()))
+}
+
+fn read_packed<R: Read + ?Sized>(reader: &mut R, len: usize) -> io::Result<vec::Vec<u8>> {
+    let mut bytes = vec![0; len];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
@@ -30,6 +30,8 @@
 //! assert_eq!(test, test_from_b);
 //! ```
 //!
+//! `use endian_codec::prelude::*;` imports all the traits and derives at once.
+//!
 //! There can be also a situation when you are forced to work with mixed-endians in one struct.
 //! ```rust
 //! use endian_codec::{PackedSize, EncodeME};
@@ -59,6 +61,117 @@
 //!
 //! ```
 //!
+//! `EncodeNE` / `DecodeNE` use the native byte order of the target, which avoids swapping bytes
+//! when both sides run on the same architecture (e.g. shared memory), but isn't portable.
+//! A `#[repr(C)]` struct whose layout matches the wire can implement `Pod` and be decoded by
+//! `decode_ne_pod`, which copies the bytes without looking at the fields.
+//!
+//! When most fields share one byte order, put `#[endian(le)]` or `#[endian = "le"]` (or `be`) on
+//! the struct and annotate only the fields that differ.
+//!
+//! `#[endian = "le"]` (or `"be"`) on a field holding a nested struct encodes the whole nested
+//! struct with its `EncodeLE` (`EncodeBE`) implementation, ignoring its own mixed-endian layout.
+//! `#[endian = "me"]` (or `"mixed"`) keeps the nested layout by using its `EncodeME`, which is
+//! useful to override a struct-level default. Fields without an attribute in a mixed-endian
+//! struct are nested the same way, including arrays such as `[Header; 3]` where every element
+//! keeps its own layout.
+//!
+//! `#[endian = "ne"]` (or `"native"`) stores a field in the byte order of the host, like
+//! `EncodeNE`.
+//!
+//! `#[endian = "pdp"]` (or `"middle"`) stores a 32-bit field in the PDP-11 order `2-1-4-3`: as
+//! a little-endian with its two 16-bit halves swapped, so `0x0A0B0C0D` is written as
+//! `[0x0B, 0x0A, 0x0D, 0x0C]`. The field has to take exactly 4 bytes, anything else fails to
+//! compile.
+//!
+//! Fields marked with `#[endian(skip)]` and `PhantomData` markers aren't part of the packed
+//! representation: they add nothing to `PACKED_LEN`, nothing is encoded for them and decoding
+//! sets them to `Default::default()`, so their type has to implement `Default`. It's useful for
+//! caches or other derived values which shouldn't touch the wire. Type parameters used only by
+//! such fields (e.g. type state) don't need to implement the derived traits.
+//!
+//! ### `#[endian_codec(...)]` attributes
+//! Derives accept additional options on struct fields:
+//! * `since = N` - field is present in the layout from version `N` onward (see `DecodeLEVersioned`).
+//! * `hex` - field bytes are written as lowercase hex ASCII digits, twice as many bytes.
+//! * `invert_bytes` - bits within every byte of the field are reversed (LSB-first on the wire),
+//!   independently of the byte order; decoding reverses them back.
+//! * `offset_from = "field"` - field holds an offset from the position of `field`; the
+//!   `PackedSize` derive adds a `resolve_<name>(&self, buf)` method returning the bytes of `buf`
+//!   it points to.
+//! * `compute = "expr"` - field is encoded as `expr` (which can use `self`) instead of its value,
+//!   decoding panics if the read value differs from `expr` evaluated on the decoded struct,
+//!   `try_decode_from_*_bytes` returns `EndianError::InvalidValue` instead.
+//! * `align_field = N` - zero padding is inserted before the field, so it starts at an offset
+//!   which is a multiple of `N`. Decoding skips the padding.
+//! * `q = N` - `f32`/`f64` field is written as a signed Q-format fixed-point number with `N`
+//!   fractional bits, stored in the smallest of `i8`/`i16`/`i32`/`i64` able to hold `N + 1` bits.
+//!   Encoding rounds to the nearest value (ties away from zero) and saturates values out of range.
+//! * `borrow` - `[u8; N]` field is borrowed from the buffer by the `<Name>Hybrid<'a>` view
+//!   generated by the `DecodeLEHybrid` derive, which decodes all other fields as usual. It avoids
+//!   copying large byte arrays. The shorter `#[borrow]` works too.
+//!
+//! and on structs:
+//! * `assert_field_order("a", "b", ...)` - fail to compile unless fields are declared in this order.
+//! * `assert_packed_len = N` - fail to compile unless `PACKED_LEN` is `N`.
+//! * `wire_order("c", "a", ...)` - fields are encoded in this order instead of the declaration
+//!   order; every field has to be listed once.
+//! * `default_on_short` - decoding a buffer shorter than `PACKED_LEN` sets every field which
+//!   doesn't fit completely to `Default::default()`, so fields can be appended over time.
+//!   `try_decode_from_*_bytes` and `decode_le_collect_errors` do the same; a buffer longer than
+//!   `PACKED_LEN` is still rejected.
+//! * `debug_bytes` - the `EncodeLE` derive also implements `Debug`, which prints the fields
+//!   followed by `le_bytes` holding the little-endian encoding as hex digits.
+//! * `checksum_range = "start..end"` - `decode_le_with_checksum` hashes only these bytes of the
+//!   packed struct, e.g. to leave out a header or the checksum itself.
+//!
+//! `bool` is stored as a single byte, `1` for `true` and `0` for `false`. Decoding maps any
+//! non-zero byte to `true`, so bytes other than `0`/`1` don't survive a round-trip.
+//!
+//! `Option<T>` is stored as a presence byte (`0` or `1`, like `bool`) followed by the value. The
+//! value bytes are always reserved, so the size stays fixed at `1 + T::PACKED_LEN`, and they're
+//! zeroed for `None`. Arrays such as `[Option<u16>; 4]` model optional fixed slots, every element
+//! with its own presence byte. `NonZeroU16` and the other `NonZero*` types are stored as their inner
+//! integer and decoding zero panics, or returns [InvalidValue](EndianError::InvalidValue) from
+//! `try_decode_from_*_bytes`. Their `Option` uses the niche instead of the presence byte: `None`
+//! is stored as zero and the size is the same as of the inner integer.
+//!
+//! Enums without fields are stored as a `u8` index of the variant (in declaration order).
+//! With an integer `#[repr(..)]`, e.g. `#[repr(u16)]`, the discriminant is stored instead, using
+//! the width of the repr type, so explicit discriminants can be used. Decoding an unknown value
+//! panics, while `try_decode_from_*_bytes` returns [InvalidValue](EndianError::InvalidValue).
+//! Derived structs implement `try_decode_from_*_bytes` by trying every field in turn, so such a
+//! value nested anywhere in a struct is reported the same way.
+//! Other tag schemes can be supplied on the enum with
+//! `#[endian_codec(tag(encode = "to_tag", decode = "from_tag"))]`, where `to_tag` is
+//! `fn(&Enum) -> T` and `from_tag` is `fn(T) -> Option<Enum>` for any codec type `T`.
+//!
+//! ```rust
+//! use endian_codec::{PackedSize, EncodeBE, DecodeBE};
+//!
+//! #[derive(Debug, PartialEq, PackedSize, EncodeBE, DecodeBE)]
+//! struct Sample {
+//!   #[endian_codec(q = 15)]
+//!   gain: f32,
+//! }
+//!
+//! let mut buf = [0; Sample::PACKED_LEN];
+//! Sample { gain: 0.5 }.encode_as_be_bytes(&mut buf);
+//! assert_eq!([0x40, 0x00], buf);
+//! ```
+//!
+//! ### Cargo features
+//! * `alloc` - `DecodeLE::decode_le_collect_errors` reporting every invalid field,
+//!   `DecodeLE::decode_le_with_path` naming the path of the first one and `encode_to_le_vec`
+//!   (`be`, `me`) returning the encoded bytes in a `Vec`.
+//! * `derive` (default) - derive macros for all traits.
+//! * `default_be` - `Encode::encode_default` / `Decode::decode_default` use big-endian instead
+//!   of little-endian.
+//! * `serde` - `SerdeLE` derive implementing `serde::Serialize` / `serde::Deserialize` as the
+//!   little-endian bytes of a type. Enables `alloc`.
+//! * `std` - enables `alloc`, decoding from `std::io::Read` streams with `decode_le_from_reader` and friends,
+//!   and `DebugLayout::debug_layout`.
+//!
 //! ### Why another crate to handle endianess?
 //! * Easy byteorder-encoding structs with multiple fields and consistent encoding
 //! * Learning how to create custom derives
@@ -69,7 +182,7 @@
 //! * [bytes] - Buf and BufMut traits that have methods to put and get primitives in the desired endian format.
 //! * [packed_struct] - Safe struct (un-) packing with bit-level control.
 //! * [simple_endian] - Instead of providing functions that convert - create types that store.
-//! variables in the desired endian format.
+//!   variables in the desired endian format.
 //! * [struct_deser] - Inspiration for this crate.
 //!
 //!
@@ -84,27 +197,242 @@
 //! [serde]:https://crates.io/crates/serde
 
 #![no_std]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+// Lets code generated by the derives refer to `endian_codec::` paths inside this crate too.
+extern crate self as endian_codec;
+#[cfg(feature = "std")]
+extern crate std;
+
 #[cfg(feature = "endian_codec_derive")]
 pub use endian_codec_derive::*;
 
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub mod collect_impl;
+mod error;
+pub mod hex;
+#[cfg(feature = "std")]
+mod io;
+mod padded_array;
+mod pod;
+pub mod prelude;
+mod scratch;
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub mod serde_impl;
+
+pub use error::EndianError;
+#[cfg(feature = "alloc")]
+pub use error::FieldError;
+#[cfg(feature = "std")]
+pub use io::{decode_be_from_reader, decode_le_from_reader, decode_me_from_reader};
+pub use padded_array::PaddedArray;
+pub use pod::{cast_ne_pod, decode_ne_pod, Pod};
+pub use scratch::Scratch;
+
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::hash::Hasher;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::net::{Ipv4Addr, SocketAddrV4};
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
+    NonZeroU32, NonZeroU64, NonZeroU8,
+};
+use core::ops::Range;
+use core::slice::ChunksExact;
+use core::time::Duration;
+
 /// Encoded as little-endian bytes.
 pub trait EncodeLE: PackedSize {
-    /// Borrow `self` and pack into `bytes` using little-endian representation.
+    /// Borrow `self` and pack into `bytes` using little-endian representation. Returns the number
+    /// of bytes written, which is always `PACKED_LEN`.
+    ///
+    /// # Panics
+    /// Panic if [PackedSize](PackedSize) represents a different size than `bytes` slice.
+    ///
+    fn encode_as_le_bytes(&self, bytes: &mut [u8]) -> usize;
+
+    /// Like [encode_as_le_bytes](EncodeLE::encode_as_le_bytes), but return an error instead of
+    /// panicking if `bytes` is too small. Only the first `PACKED_LEN` bytes are written if `bytes`
+    /// is longer, so a value can be written at the start of a larger packet buffer.
+    /// [try_decode_from_le_bytes](DecodeLE::try_decode_from_le_bytes) rejects extra bytes instead.
+    ///
+    /// # Errors
+    ///
+    /// Return [BufferTooSmall](EndianError::BufferTooSmall) if `bytes` holds fewer than
+    /// `PACKED_LEN` bytes.
+    #[inline]
+    fn try_encode_as_le_bytes(&self, bytes: &mut [u8]) -> Result<usize, EndianError> {
+        match bytes.get_mut(..Self::PACKED_LEN) {
+            Some(bytes) => Ok(self.encode_as_le_bytes(bytes)),
+            None => Err(EndianError::BufferTooSmall {
+                needed: Self::PACKED_LEN,
+                got: bytes.len(),
+            }),
+        }
+    }
+
+    /// Like [encode_as_le_bytes](EncodeLE::encode_as_le_bytes), but write into `bytes` starting
+    /// at `offset`. Returns the offset right after the written bytes, where the next value starts.
+    ///
+    /// # Panics
+    ///
+    /// Panic if `bytes` holds fewer than `offset + PACKED_LEN` bytes.
+    #[inline]
+    fn encode_as_le_bytes_at(&self, bytes: &mut [u8], offset: usize) -> usize {
+        let range = range_at(bytes.len(), offset, Self::PACKED_LEN);
+        offset + self.encode_as_le_bytes(&mut bytes[range])
+    }
+
+    /// Like [encode_as_le_bytes](EncodeLE::encode_as_le_bytes), but also pass the written bytes
+    /// to `sink`, e.g. to compute a checksum of a frame while encoding it piece by piece.
+    ///
+    /// ```rust
+    /// use endian_codec::{ByteSink, EncodeLE};
+    ///
+    /// struct Sum(u32);
+    ///
+    /// impl ByteSink for Sum {
+    ///     fn write(&mut self, bytes: &[u8]) {
+    ///         self.0 += bytes.iter().map(|&b| u32::from(b)).sum::<u32>();
+    ///     }
+    /// }
+    ///
+    /// let mut sum = Sum(0);
+    /// let mut bytes = [0; 4];
+    /// 0x0102_0304u32.encode_as_le_bytes_to_sink(&mut bytes, &mut sum);
+    /// assert_eq!(10, sum.0);
+    /// ```
     ///
     /// # Panics
+    ///
     /// Panic if [PackedSize](PackedSize) represents a different size than `bytes` slice.
+    #[inline]
+    fn encode_as_le_bytes_to_sink<S: ByteSink + ?Sized>(
+        &self,
+        bytes: &mut [u8],
+        sink: &mut S,
+    ) -> usize {
+        let len = self.encode_as_le_bytes(bytes);
+        sink.write(&bytes[..len]);
+        len
+    }
+
+    /// Encode `self` into an array of `N` little-endian bytes.
     ///
-    fn encode_as_le_bytes(&self, bytes: &mut [u8]);
+    /// `N` has to be equal to `PACKED_LEN`, which is checked at compile time (by `cargo build`,
+    /// `cargo check` doesn't evaluate it).
+    ///
+    /// ```rust
+    /// use endian_codec::EncodeLE;
+    ///
+    /// let bytes: [u8; 4] = 0x0102_0304u32.encode_le_array();
+    /// assert_eq!([4, 3, 2, 1], bytes);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use endian_codec::EncodeLE;
+    ///
+    /// let bytes: [u8; 3] = 0x0102_0304u32.encode_le_array();
+    /// ```
+    #[inline]
+    fn encode_le_array<const N: usize>(&self) -> [u8; N] {
+        let () = ArrayFits::<Self, N>::OK;
+        let mut bytes = [0; N];
+        self.encode_as_le_bytes(&mut bytes);
+        bytes
+    }
+
+    /// Encode `self` into a newly allocated vector of `PACKED_LEN` little-endian bytes.
+    #[cfg(feature = "alloc")]
+    fn encode_to_le_vec(&self) -> alloc::vec::Vec<u8> {
+        let mut bytes = alloc::vec![0; Self::PACKED_LEN];
+        self.encode_as_le_bytes(&mut bytes);
+        bytes
+    }
 }
 
 /// Encoded as big-endian bytes.
 pub trait EncodeBE: PackedSize {
-    /// Borrow `self` and pack into `bytes` using big-endian representation.
+    /// Borrow `self` and pack into `bytes` using big-endian representation. Returns the number of
+    /// bytes written, which is always `PACKED_LEN`.
+    ///
+    /// # Panics
+    ///
+    /// Panic if [PackedSize](PackedSize) represents a different size than `bytes` slice.
+    fn encode_as_be_bytes(&self, bytes: &mut [u8]) -> usize;
+
+    /// Like [encode_as_be_bytes](EncodeBE::encode_as_be_bytes), but return an error instead of
+    /// panicking if `bytes` is too small. Only the first `PACKED_LEN` bytes are written if `bytes`
+    /// is longer, so a value can be written at the start of a larger packet buffer.
+    /// [try_decode_from_be_bytes](DecodeBE::try_decode_from_be_bytes) rejects extra bytes instead.
+    ///
+    /// # Errors
+    ///
+    /// Return [BufferTooSmall](EndianError::BufferTooSmall) if `bytes` holds fewer than
+    /// `PACKED_LEN` bytes.
+    #[inline]
+    fn try_encode_as_be_bytes(&self, bytes: &mut [u8]) -> Result<usize, EndianError> {
+        match bytes.get_mut(..Self::PACKED_LEN) {
+            Some(bytes) => Ok(self.encode_as_be_bytes(bytes)),
+            None => Err(EndianError::BufferTooSmall {
+                needed: Self::PACKED_LEN,
+                got: bytes.len(),
+            }),
+        }
+    }
+
+    /// Like [encode_as_be_bytes](EncodeBE::encode_as_be_bytes), but write into `bytes` starting
+    /// at `offset`. Returns the offset right after the written bytes, where the next value starts.
+    ///
+    /// # Panics
+    ///
+    /// Panic if `bytes` holds fewer than `offset + PACKED_LEN` bytes.
+    #[inline]
+    fn encode_as_be_bytes_at(&self, bytes: &mut [u8], offset: usize) -> usize {
+        let range = range_at(bytes.len(), offset, Self::PACKED_LEN);
+        offset + self.encode_as_be_bytes(&mut bytes[range])
+    }
+
+    /// Like [encode_as_be_bytes](EncodeBE::encode_as_be_bytes), but also pass the written bytes
+    /// to `sink`, e.g. to compute a checksum of a frame while encoding it piece by piece.
     ///
     /// # Panics
     ///
     /// Panic if [PackedSize](PackedSize) represents a different size than `bytes` slice.
-    fn encode_as_be_bytes(&self, bytes: &mut [u8]);
+    #[inline]
+    fn encode_as_be_bytes_to_sink<S: ByteSink + ?Sized>(
+        &self,
+        bytes: &mut [u8],
+        sink: &mut S,
+    ) -> usize {
+        let len = self.encode_as_be_bytes(bytes);
+        sink.write(&bytes[..len]);
+        len
+    }
+
+    /// Encode `self` into an array of `N` big-endian bytes.
+    ///
+    /// `N` has to be equal to `PACKED_LEN`, which is checked at compile time (by `cargo build`,
+    /// `cargo check` doesn't evaluate it).
+    #[inline]
+    fn encode_be_array<const N: usize>(&self) -> [u8; N] {
+        let () = ArrayFits::<Self, N>::OK;
+        let mut bytes = [0; N];
+        self.encode_as_be_bytes(&mut bytes);
+        bytes
+    }
+
+    /// Encode `self` into a newly allocated vector of `PACKED_LEN` big-endian bytes.
+    #[cfg(feature = "alloc")]
+    fn encode_to_be_vec(&self) -> alloc::vec::Vec<u8> {
+        let mut bytes = alloc::vec![0; Self::PACKED_LEN];
+        self.encode_as_be_bytes(&mut bytes);
+        bytes
+    }
 }
 
 /// Encode using mixed-endian bytes.
@@ -112,15 +440,101 @@ pub trait EncodeBE: PackedSize {
 /// # Note
 /// If you only use big-/little-endians, consider using [EncodeBE](EncodeBE) / [EncodeLE](EncodeLE) traits instead.
 pub trait EncodeME: PackedSize {
-    /// Borrow `self` and pack into `bytes` using mixed(custom)-endian representation.
+    /// Borrow `self` and pack into `bytes` using mixed(custom)-endian representation. Returns the
+    /// number of bytes written, which is always `PACKED_LEN`.
+    ///
+    /// # Panics
+    ///
+    /// Panic if [PackedSize](PackedSize) represents a different size than `bytes` slice.
+    fn encode_as_me_bytes(&self, bytes: &mut [u8]) -> usize;
+
+    /// Like [encode_as_me_bytes](EncodeME::encode_as_me_bytes), but return an error instead of
+    /// panicking if `bytes` is too small. Only the first `PACKED_LEN` bytes are written if `bytes`
+    /// is longer, so a value can be written at the start of a larger packet buffer.
+    /// [try_decode_from_me_bytes](DecodeME::try_decode_from_me_bytes) rejects extra bytes instead.
+    ///
+    /// # Errors
+    ///
+    /// Return [BufferTooSmall](EndianError::BufferTooSmall) if `bytes` holds fewer than
+    /// `PACKED_LEN` bytes.
+    #[inline]
+    fn try_encode_as_me_bytes(&self, bytes: &mut [u8]) -> Result<usize, EndianError> {
+        match bytes.get_mut(..Self::PACKED_LEN) {
+            Some(bytes) => Ok(self.encode_as_me_bytes(bytes)),
+            None => Err(EndianError::BufferTooSmall {
+                needed: Self::PACKED_LEN,
+                got: bytes.len(),
+            }),
+        }
+    }
+
+    /// Like [encode_as_me_bytes](EncodeME::encode_as_me_bytes), but write into `bytes` starting
+    /// at `offset`. Returns the offset right after the written bytes, where the next value starts.
+    ///
+    /// # Panics
+    ///
+    /// Panic if `bytes` holds fewer than `offset + PACKED_LEN` bytes.
+    #[inline]
+    fn encode_as_me_bytes_at(&self, bytes: &mut [u8], offset: usize) -> usize {
+        let range = range_at(bytes.len(), offset, Self::PACKED_LEN);
+        offset + self.encode_as_me_bytes(&mut bytes[range])
+    }
+
+    /// Like [encode_as_me_bytes](EncodeME::encode_as_me_bytes), but also pass the written bytes
+    /// to `sink`, e.g. to compute a checksum of a frame while encoding it piece by piece.
     ///
     /// # Panics
     ///
     /// Panic if [PackedSize](PackedSize) represents a different size than `bytes` slice.
-    fn encode_as_me_bytes(&self, bytes: &mut [u8]);
+    #[inline]
+    fn encode_as_me_bytes_to_sink<S: ByteSink + ?Sized>(
+        &self,
+        bytes: &mut [u8],
+        sink: &mut S,
+    ) -> usize {
+        let len = self.encode_as_me_bytes(bytes);
+        sink.write(&bytes[..len]);
+        len
+    }
+
+    /// Encode `self` into an array of `N` mixed-endian bytes.
+    ///
+    /// `N` has to be equal to `PACKED_LEN`, which is checked at compile time (by `cargo build`,
+    /// `cargo check` doesn't evaluate it).
+    #[inline]
+    fn encode_me_array<const N: usize>(&self) -> [u8; N] {
+        let () = ArrayFits::<Self, N>::OK;
+        let mut bytes = [0; N];
+        self.encode_as_me_bytes(&mut bytes);
+        bytes
+    }
+
+    /// Encode `self` into a newly allocated vector of `PACKED_LEN` mixed-endian bytes.
+    #[cfg(feature = "alloc")]
+    fn encode_to_me_vec(&self) -> alloc::vec::Vec<u8> {
+        let mut bytes = alloc::vec![0; Self::PACKED_LEN];
+        self.encode_as_me_bytes(&mut bytes);
+        bytes
+    }
 }
 
 /// Decode from bytes stored as a little-endian.
+///
+/// For types without generic parameters the derive also implements `From<&[u8; PACKED_LEN]>`,
+/// which decodes little-endian bytes without any size check at runtime.
+///
+/// ```rust
+/// use endian_codec::{PackedSize, DecodeLE};
+///
+/// #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE)]
+/// struct Version {
+///   major: u16,
+///   minor: u16,
+/// }
+///
+/// let buf = [1, 0, 2, 0];
+/// assert_eq!(Version { major: 1, minor: 2 }, Version::from(&buf));
+/// ```
 pub trait DecodeLE: PackedSize {
     /// Read `bytes` slice packed as little-endian bytes and create `Self` from them
     ///
@@ -128,6 +542,163 @@ pub trait DecodeLE: PackedSize {
     ///
     /// Panic if [PackedSize](PackedSize) represents a different size than `bytes` slice.
     fn decode_from_le_bytes(bytes: &[u8]) -> Self;
+
+    /// Like [decode_from_le_bytes](DecodeLE::decode_from_le_bytes), but return an error instead of
+    /// a panic if `bytes` doesn't hold exactly `PACKED_LEN` bytes.
+    ///
+    /// Unlike [try_encode_as_le_bytes](EncodeLE::try_encode_as_le_bytes), a longer buffer is an
+    /// error, as the extra bytes would be silently ignored.
+    ///
+    /// # Errors
+    ///
+    /// Return [BufferTooSmall](EndianError::BufferTooSmall) or
+    /// [BufferTooLarge](EndianError::BufferTooLarge) if the length of `bytes` differs from
+    /// `PACKED_LEN`, or the error of an invalid value.
+    #[inline]
+    fn try_decode_from_le_bytes(bytes: &[u8]) -> Result<Self, EndianError>
+    where
+        Self: Sized,
+    {
+        check_decode_len(bytes.len(), Self::PACKED_LEN)?;
+        Ok(Self::decode_from_le_bytes(bytes))
+    }
+
+    /// Like [try_decode_from_le_bytes](DecodeLE::try_decode_from_le_bytes), but pull exactly
+    /// `PACKED_LEN` bytes from `iter`, e.g. a byte-at-a-time reader. Pass `iter.by_ref()` to keep
+    /// reading after the value.
+    ///
+    /// ```rust
+    /// use endian_codec::DecodeLE;
+    ///
+    /// let mut uart = [0x34, 0x12, 0x78, 0x56, 0x01].iter().copied();
+    /// assert_eq!(Ok(0x1234), u16::decode_from_le_iter::<2>(uart.by_ref()));
+    /// assert_eq!(Ok(0x5678), u16::decode_from_le_iter::<2>(uart.by_ref()));
+    /// assert!(u16::decode_from_le_iter::<2>(uart).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Return [BufferTooSmall](EndianError::BufferTooSmall) if `iter` ends early, the error of
+    /// [try_decode_from_le_bytes](DecodeLE::try_decode_from_le_bytes) otherwise.
+    ///
+    /// The bytes are collected in an array of `N` bytes on the stack. `N` has to be equal to
+    /// `PACKED_LEN`, which is checked at compile time.
+    fn decode_from_le_iter<const N: usize>(
+        iter: impl Iterator<Item = u8>,
+    ) -> Result<Self, EndianError>
+    where
+        Self: Sized,
+    {
+        let () = ArrayFits::<Self, N>::OK;
+        let mut bytes = [0; N];
+        fill_from_byte_iter(iter, &mut bytes)?;
+        Self::try_decode_from_le_bytes(&bytes)
+    }
+
+    /// Like [decode_from_le_bytes](DecodeLE::decode_from_le_bytes), but read from `bytes`
+    /// starting at `offset`. Returns the value and the offset right after its bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panic if `bytes` holds fewer than `offset + PACKED_LEN` bytes.
+    #[inline]
+    fn decode_from_le_bytes_at(bytes: &[u8], offset: usize) -> (Self, usize)
+    where
+        Self: Sized,
+    {
+        let range = range_at(bytes.len(), offset, Self::PACKED_LEN);
+        let end = range.end;
+        (Self::decode_from_le_bytes(&bytes[range]), end)
+    }
+
+    /// Like [try_decode_from_le_bytes](DecodeLE::try_decode_from_le_bytes), but also return
+    /// [NonCanonical](EndianError::NonCanonical) if encoding the decoded value doesn't give back
+    /// `bytes`, e.g. padding isn't zeroed. Use it to validate signed data.
+    ///
+    /// The value is re-encoded through [HashLE](HashLE) and compared piece by piece, so no
+    /// buffer is needed whatever the size of `Self`.
+    fn decode_le_canonical(bytes: &[u8]) -> Result<Self, EndianError>
+    where
+        Self: HashLE + Sized,
+    {
+        let value = Self::try_decode_from_le_bytes(bytes)?;
+        let mut compare = CompareBytes {
+            expected: bytes,
+            equal: true,
+        };
+        value.hash_le(&mut compare);
+        if compare.equal && compare.expected.is_empty() {
+            Ok(value)
+        } else {
+            Err(EndianError::NonCanonical)
+        }
+    }
+
+    /// Like [try_decode_from_le_bytes](DecodeLE::try_decode_from_le_bytes), but derived structs
+    /// decode every field and report all which failed, named by their path, instead of stopping
+    /// at the first one.
+    #[cfg(feature = "alloc")]
+    fn decode_le_collect_errors(bytes: &[u8]) -> Result<Self, alloc::vec::Vec<FieldError>>
+    where
+        Self: Sized,
+    {
+        Self::try_decode_from_le_bytes(bytes).map_err(|error| {
+            alloc::vec![FieldError {
+                field: alloc::string::String::new(),
+                error,
+            }]
+        })
+    }
+
+    /// Like [try_decode_from_le_bytes](DecodeLE::try_decode_from_le_bytes), but the error names
+    /// the path of the field which failed, e.g. `header.kind` of a nested struct. It is the first
+    /// error reported by [decode_le_collect_errors](DecodeLE::decode_le_collect_errors).
+    #[cfg(feature = "alloc")]
+    fn decode_le_with_path(bytes: &[u8]) -> Result<Self, FieldError>
+    where
+        Self: Sized,
+    {
+        Self::decode_le_collect_errors(bytes).map_err(|mut errors| errors.swap_remove(0))
+    }
+
+    /// Like [decode_from_le_bytes](DecodeLE::decode_from_le_bytes), but return `None` if every
+    /// byte of `bytes` is zero, which many formats use to mark an absent record.
+    ///
+    /// ```rust
+    /// use endian_codec::DecodeLE;
+    ///
+    /// assert_eq!(None, u32::decode_le_nonzero(&[0, 0, 0, 0]));
+    /// assert_eq!(Some(1), u32::decode_le_nonzero(&[1, 0, 0, 0]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panic if [PackedSize](PackedSize) represents a different size than `bytes` slice.
+    fn decode_le_nonzero(bytes: &[u8]) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if bytes.iter().all(|&b| b == 0) {
+            None
+        } else {
+            Some(Self::decode_from_le_bytes(bytes))
+        }
+    }
+
+    /// Like [decode_from_le_bytes](DecodeLE::decode_from_le_bytes), but also return the hash of
+    /// `bytes` computed by `hasher`, so it can be compared with a separately received checksum.
+    ///
+    /// # Panics
+    ///
+    /// Panic if [PackedSize](PackedSize) represents a different size than `bytes` slice.
+    fn decode_le_with_checksum<H: Hasher>(bytes: &[u8], mut hasher: H) -> (Self, u64)
+    where
+        Self: Sized,
+    {
+        let value = Self::decode_from_le_bytes(bytes);
+        hasher.write(bytes);
+        (value, hasher.finish())
+    }
 }
 
 /// Decode from bytes stored as a big-endian.
@@ -138,6 +709,65 @@ pub trait DecodeBE: PackedSize {
     ///
     /// Panic if [PackedSize](PackedSize) represents a different size than `bytes` slice.
     fn decode_from_be_bytes(bytes: &[u8]) -> Self;
+
+    /// Like [decode_from_be_bytes](DecodeBE::decode_from_be_bytes), but return an error instead of
+    /// a panic if `bytes` doesn't hold exactly `PACKED_LEN` bytes.
+    ///
+    /// Unlike [try_encode_as_be_bytes](EncodeBE::try_encode_as_be_bytes), a longer buffer is an
+    /// error, as the extra bytes would be silently ignored.
+    ///
+    /// # Errors
+    ///
+    /// Return [BufferTooSmall](EndianError::BufferTooSmall) or
+    /// [BufferTooLarge](EndianError::BufferTooLarge) if the length of `bytes` differs from
+    /// `PACKED_LEN`, or the error of an invalid value.
+    #[inline]
+    fn try_decode_from_be_bytes(bytes: &[u8]) -> Result<Self, EndianError>
+    where
+        Self: Sized,
+    {
+        check_decode_len(bytes.len(), Self::PACKED_LEN)?;
+        Ok(Self::decode_from_be_bytes(bytes))
+    }
+
+    /// Like [try_decode_from_be_bytes](DecodeBE::try_decode_from_be_bytes), but pull exactly
+    /// `PACKED_LEN` bytes from `iter`, e.g. a byte-at-a-time reader. Pass `iter.by_ref()` to keep
+    /// reading after the value.
+    ///
+    /// # Errors
+    ///
+    /// Return [BufferTooSmall](EndianError::BufferTooSmall) if `iter` ends early, the error of
+    /// [try_decode_from_be_bytes](DecodeBE::try_decode_from_be_bytes) otherwise.
+    ///
+    /// The bytes are collected in an array of `N` bytes on the stack. `N` has to be equal to
+    /// `PACKED_LEN`, which is checked at compile time.
+    fn decode_from_be_iter<const N: usize>(
+        iter: impl Iterator<Item = u8>,
+    ) -> Result<Self, EndianError>
+    where
+        Self: Sized,
+    {
+        let () = ArrayFits::<Self, N>::OK;
+        let mut bytes = [0; N];
+        fill_from_byte_iter(iter, &mut bytes)?;
+        Self::try_decode_from_be_bytes(&bytes)
+    }
+
+    /// Like [decode_from_be_bytes](DecodeBE::decode_from_be_bytes), but read from `bytes`
+    /// starting at `offset`. Returns the value and the offset right after its bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panic if `bytes` holds fewer than `offset + PACKED_LEN` bytes.
+    #[inline]
+    fn decode_from_be_bytes_at(bytes: &[u8], offset: usize) -> (Self, usize)
+    where
+        Self: Sized,
+    {
+        let range = range_at(bytes.len(), offset, Self::PACKED_LEN);
+        let end = range.end;
+        (Self::decode_from_be_bytes(&bytes[range]), end)
+    }
 }
 
 /// Decode from bytes stored as a mixed-endian.
@@ -151,413 +781,5485 @@ pub trait DecodeME: PackedSize {
     ///
     /// Panic if [PackedSize](PackedSize) represents a different size than `bytes` slice.
     fn decode_from_me_bytes(bytes: &[u8]) -> Self;
+
+    /// Like [decode_from_me_bytes](DecodeME::decode_from_me_bytes), but return an error instead of
+    /// a panic if `bytes` doesn't hold exactly `PACKED_LEN` bytes.
+    ///
+    /// Unlike [try_encode_as_me_bytes](EncodeME::try_encode_as_me_bytes), a longer buffer is an
+    /// error, as the extra bytes would be silently ignored.
+    ///
+    /// # Errors
+    ///
+    /// Return [BufferTooSmall](EndianError::BufferTooSmall) or
+    /// [BufferTooLarge](EndianError::BufferTooLarge) if the length of `bytes` differs from
+    /// `PACKED_LEN`, or the error of an invalid value.
+    #[inline]
+    fn try_decode_from_me_bytes(bytes: &[u8]) -> Result<Self, EndianError>
+    where
+        Self: Sized,
+    {
+        check_decode_len(bytes.len(), Self::PACKED_LEN)?;
+        Ok(Self::decode_from_me_bytes(bytes))
+    }
+
+    /// Like [try_decode_from_me_bytes](DecodeME::try_decode_from_me_bytes), but pull exactly
+    /// `PACKED_LEN` bytes from `iter`, e.g. a byte-at-a-time reader. Pass `iter.by_ref()` to keep
+    /// reading after the value.
+    ///
+    /// # Errors
+    ///
+    /// Return [BufferTooSmall](EndianError::BufferTooSmall) if `iter` ends early, the error of
+    /// [try_decode_from_me_bytes](DecodeME::try_decode_from_me_bytes) otherwise.
+    ///
+    /// The bytes are collected in an array of `N` bytes on the stack. `N` has to be equal to
+    /// `PACKED_LEN`, which is checked at compile time.
+    fn decode_from_me_iter<const N: usize>(
+        iter: impl Iterator<Item = u8>,
+    ) -> Result<Self, EndianError>
+    where
+        Self: Sized,
+    {
+        let () = ArrayFits::<Self, N>::OK;
+        let mut bytes = [0; N];
+        fill_from_byte_iter(iter, &mut bytes)?;
+        Self::try_decode_from_me_bytes(&bytes)
+    }
+
+    /// Like [decode_from_me_bytes](DecodeME::decode_from_me_bytes), but read from `bytes`
+    /// starting at `offset`. Returns the value and the offset right after its bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panic if `bytes` holds fewer than `offset + PACKED_LEN` bytes.
+    #[inline]
+    fn decode_from_me_bytes_at(bytes: &[u8], offset: usize) -> (Self, usize)
+    where
+        Self: Sized,
+    {
+        let range = range_at(bytes.len(), offset, Self::PACKED_LEN);
+        let end = range.end;
+        (Self::decode_from_me_bytes(&bytes[range]), end)
+    }
 }
 
-/// Represents size of a struct as packed bytes.
-///
-/// At this moment all settings with [repr](https://doc.rust-lang.org/nomicon/other-reprs.html)
-/// attribute are ignored.
-///
-/// In other words if struct is marked as `repr(packed)` attribute, `std::mem::sizeof<T>()` should return the
-/// same value as <T as PackedSize>::PACKED_LEN.
-///
-/// ```
-/// // On a 64-bit machine, the size of struct A can be 16 bytes to make it more optimized for speed.
-/// // but `PACKED_LEN` must be set to 12 bytes.
-/// struct A {
-///   p: i32,
-///   v: i64,
-/// }
-/// ```
+/// Encode using native-endian bytes, the byte order of the target.
 ///
-pub trait PackedSize {
-    const PACKED_LEN: usize;
+/// # Note
+/// The layout differs between targets, use it only when both sides run on the same architecture,
+/// e.g. for shared memory between processes on the same host.
+pub trait EncodeNE: PackedSize {
+    /// Borrow `self` and pack into `bytes` using native-endian representation. Returns the number
+    /// of bytes written, which is always `PACKED_LEN`.
+    ///
+    /// # Panics
+    ///
+    /// Panic if [PackedSize](PackedSize) represents a different size than `bytes` slice.
+    fn encode_as_ne_bytes(&self, bytes: &mut [u8]) -> usize;
 }
 
-macro_rules! impl_codec_for_primitives {
-    ($type:ty, $byte_len:expr) => {
-        impl PackedSize for $type {
-            const PACKED_LEN: usize = $byte_len;
-        }
+/// Decode from bytes stored as a native-endian, the byte order of the target.
+///
+/// # Note
+/// The layout differs between targets, use it only when both sides run on the same architecture.
+pub trait DecodeNE: PackedSize {
+    /// Read `bytes` slice packed as native-endian bytes and create `Self` from them
+    ///
+    /// # Panics
+    ///
+    /// Panic if [PackedSize](PackedSize) represents a different size than `bytes` slice.
+    fn decode_from_ne_bytes(bytes: &[u8]) -> Self;
 
-        impl EncodeLE for $type {
-            #[inline]
-            fn encode_as_le_bytes(&self, bytes: &mut [u8]) {
-                bytes.copy_from_slice(&(self.to_le_bytes()))
-            }
-        }
+    /// Like [decode_from_ne_bytes](DecodeNE::decode_from_ne_bytes), but return an error instead of
+    /// a panic if `bytes` doesn't hold exactly `PACKED_LEN` bytes.
+    ///
+    /// # Errors
+    ///
+    /// Return [BufferTooSmall](EndianError::BufferTooSmall) or
+    /// [BufferTooLarge](EndianError::BufferTooLarge) if the length of `bytes` differs from
+    /// `PACKED_LEN`, or the error of an invalid value.
+    #[inline]
+    fn try_decode_from_ne_bytes(bytes: &[u8]) -> Result<Self, EndianError>
+    where
+        Self: Sized,
+    {
+        check_decode_len(bytes.len(), Self::PACKED_LEN)?;
+        Ok(Self::decode_from_ne_bytes(bytes))
+    }
+}
 
-        impl EncodeBE for $type {
-            #[inline]
-            fn encode_as_be_bytes(&self, bytes: &mut [u8]) {
-                bytes.copy_from_slice(&(self.to_be_bytes()))
-            }
-        }
+// Checks that the bytes passed to `write` continue `expected`.
+struct CompareBytes<'a> {
+    // bytes not compared yet
+    expected: &'a [u8],
+    equal: bool,
+}
 
-        impl DecodeLE for $type {
-            #[inline]
-            fn decode_from_le_bytes(bytes: &[u8]) -> Self {
-                let mut arr = [0; $byte_len];
-                arr.copy_from_slice(&bytes);
-                Self::from_le_bytes(arr)
-            }
+impl Hasher for CompareBytes<'_> {
+    fn write(&mut self, bytes: &[u8]) {
+        match self.expected.strip_prefix(bytes) {
+            Some(rest) if self.equal => self.expected = rest,
+            _ => self.equal = false,
         }
+    }
 
-        impl DecodeBE for $type {
-            #[inline]
-            fn decode_from_be_bytes(bytes: &[u8]) -> Self {
-                let mut arr = [0; $byte_len];
-                arr.copy_from_slice(&bytes);
-                Self::from_be_bytes(arr)
-            }
-        }
-    };
+    fn finish(&self) -> u64 {
+        u64::from(self.equal)
+    }
 }
 
-impl_codec_for_primitives!(u8, 1);
+// Fill the whole `bytes` from `iter`.
+fn fill_from_byte_iter<I: Iterator<Item = u8>>(
+    iter: I,
+    bytes: &mut [u8],
+) -> Result<(), EndianError> {
+    let mut got = 0;
+    for (slot, byte) in bytes.iter_mut().zip(iter) {
+        *slot = byte;
+        got += 1;
+    }
+    if got < bytes.len() {
+        return Err(EndianError::BufferTooSmall {
+            needed: bytes.len(),
+            got,
+        });
+    }
+    Ok(())
+}
+
+struct ArrayFits<T: ?Sized, const N: usize>(PhantomData<T>);
+
+impl<T: PackedSize + ?Sized, const N: usize> ArrayFits<T, N> {
+    const OK: () = assert!(N == T::PACKED_LEN, "array length differs from PACKED_LEN");
+}
+
+// Feed `len` zero bytes into `state`, e.g. padding written by `EncodeLE`, without a buffer of
+// that length.
+#[doc(hidden)]
+pub fn hash_zeros<H: Hasher + ?Sized>(state: &mut H, mut len: usize) {
+    const ZEROS: [u8; 64] = [0; 64];
+    while len > 0 {
+        let chunk = len.min(ZEROS.len());
+        state.write(&ZEROS[..chunk]);
+        len -= chunk;
+    }
+}
+
+// End of a field in the code generated by the derives. Being a `const fn`, `PACKED_LEN` evaluates
+// it at compile time, so a layout overflowing `usize` fails to compile instead of wrapping.
+#[doc(hidden)]
+pub const fn field_end(offset: usize, size: usize) -> usize {
+    match offset.checked_add(size) {
+        Some(end) => end,
+        None => panic!("field offset overflows usize"),
+    }
+}
+
+struct PdpWord<T: ?Sized>(PhantomData<T>);
+
+impl<T: PackedSize + ?Sized> PdpWord<T> {
+    const OK: () = assert!(
+        T::PACKED_LEN == 4,
+        "middle-endian (pdp) field must take 4 bytes"
+    );
+}
+
+// Fields with `#[endian = "pdp"]` are encoded as a little-endian with the two 16-bit halves
+// swapped, i.e. bytes in the 2-1-4-3 order.
+#[doc(hidden)]
+pub fn encode_as_pdp_bytes<T: EncodeLE + ?Sized>(value: &T, bytes: &mut [u8]) -> usize {
+    let () = PdpWord::<T>::OK;
+    let len = value.encode_as_le_bytes(bytes);
+    swap_pdp_halves(bytes);
+    len
+}
+
+#[doc(hidden)]
+pub fn decode_from_pdp_bytes<T: DecodeLE>(bytes: &[u8]) -> T {
+    let () = PdpWord::<T>::OK;
+    assert_eq!(T::PACKED_LEN, bytes.len());
+    let mut le = [0; 4];
+    le.copy_from_slice(bytes);
+    swap_pdp_halves(&mut le);
+    T::decode_from_le_bytes(&le)
+}
+
+#[doc(hidden)]
+pub fn try_decode_from_pdp_bytes<T: DecodeLE>(bytes: &[u8]) -> Result<T, EndianError> {
+    let () = PdpWord::<T>::OK;
+    check_decode_len(bytes.len(), T::PACKED_LEN)?;
+    let mut le = [0; 4];
+    le.copy_from_slice(bytes);
+    swap_pdp_halves(&mut le);
+    T::try_decode_from_le_bytes(&le)
+}
+
+fn swap_pdp_halves(bytes: &mut [u8]) {
+    let (low, high) = bytes.split_at_mut(2);
+    low.swap_with_slice(high);
+}
+
+// Used by the derives for fields with `#[endian_codec(invert_bytes)]`.
+#[doc(hidden)]
+pub fn invert_bytes(bytes: &mut [u8]) {
+    for byte in bytes {
+        *byte = byte.reverse_bits();
+    }
+}
+
+// Range of `len` bytes at `offset` of a buffer of `buf_len` bytes, used by the `*_at` methods.
+fn range_at(buf_len: usize, offset: usize, len: usize) -> Range<usize> {
+    match offset.checked_add(len) {
+        Some(end) if end <= buf_len => offset..end,
+        _ => panic!(
+            "buffer holds fewer than offset + PACKED_LEN bytes: {} < {} + {}",
+            buf_len, offset, len
+        ),
+    }
+}
+
+#[doc(hidden)]
+pub fn check_decode_len(got: usize, needed: usize) -> Result<(), EndianError> {
+    match got.cmp(&needed) {
+        Ordering::Less => Err(EndianError::BufferTooSmall { needed, got }),
+        Ordering::Greater => Err(EndianError::BufferTooLarge { needed, got }),
+        Ordering::Equal => Ok(()),
+    }
+}
+
+/// Byte order of a single field chosen at runtime by [DecodeWithEndian](DecodeWithEndian).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endian {
+    /// Field is stored as a little-endian.
+    Little,
+    /// Field is stored as a big-endian.
+    Big,
+}
+
+/// Decode from bytes where endianness of every field is decided at runtime.
+///
+/// This is meant for formats where a byte order of some fields depends on data, e.g. on a flag
+/// decoded earlier.
+///
+/// ```rust
+/// use endian_codec::{DecodeBE, DecodeLE, DecodeWithEndian, Endian, PackedSize};
+///
+/// #[derive(Debug, PartialEq, Eq, PackedSize, DecodeWithEndian)]
+/// struct Record {
+///   kind: u16,
+///   value: u16,
+/// }
+///
+/// // only `value` (field with index 1) is stored as a little-endian
+/// let record = Record::decode_with_endian(&[0, 1, 2, 0], |i| match i {
+///     1 => Endian::Little,
+///     _ => Endian::Big,
+/// });
+/// assert_eq!(Record { kind: 1, value: 2 }, record);
+/// ```
+pub trait DecodeWithEndian: PackedSize + Sized {
+    /// Read `bytes` slice and create `Self` from them. Each field is decoded using byte order
+    /// returned by `endian` called with the field index (in declaration order).
+    ///
+    /// # Panics
+    ///
+    /// Panic if [PackedSize](PackedSize) represents a different size than `bytes` slice.
+    fn decode_with_endian<F>(bytes: &[u8], endian: F) -> Self
+    where
+        F: FnMut(usize) -> Endian;
+}
+
+/// Feed little-endian bytes into a [Hasher](core::hash::Hasher).
+///
+/// Bytes are the same as written by [EncodeLE](EncodeLE), so the hash is stable across hosts
+/// and doesn't depend on the in-memory layout, yet no buffer is needed.
+///
+/// ```rust
+/// use endian_codec::HashLE;
+/// # use std::collections::hash_map::DefaultHasher;
+/// use core::hash::Hasher;
+///
+/// #[derive(HashLE)]
+/// struct Version {
+///   major: u16,
+///   minor: u16,
+/// }
+///
+/// let mut hasher = DefaultHasher::new();
+/// Version { major: 1, minor: 2 }.hash_le(&mut hasher);
+/// let hash = hasher.finish();
+///
+/// let mut hasher = DefaultHasher::new();
+/// hasher.write(&[1, 0, 2, 0]);
+/// assert_eq!(hash, hasher.finish());
+/// ```
+pub trait HashLE {
+    /// Write `self` as little-endian bytes into `state`.
+    fn hash_le<H: Hasher>(&self, state: &mut H);
+}
+
+/// Consumer of encoded bytes, e.g. a CRC or another checksum computed incrementally.
+///
+/// Unlike [Hasher](core::hash::Hasher) it doesn't need to produce a `u64`, so any checksum can
+/// implement it. Bytes are passed in the order they were encoded by
+/// [encode_as_le_bytes_to_sink](EncodeLE::encode_as_le_bytes_to_sink) and friends.
+pub trait ByteSink {
+    /// Consume the next encoded `bytes`.
+    fn write(&mut self, bytes: &[u8]);
+}
+
+/// Convert packed bytes between little- and big-endian layout in place.
+///
+/// Flipping bytes of a buffer holding `Self` encoded by [EncodeLE](EncodeLE) gives the same
+/// bytes [EncodeBE](EncodeBE) would produce and vice versa, without decoding the value. Byte
+/// arrays and single bytes are left untouched. Derived implementations flip each field in turn.
+///
+/// ```rust
+/// use endian_codec::{PackedSize, SwapEndian};
+///
+/// #[derive(PackedSize, SwapEndian)]
+/// struct Header {
+///   len: u16,
+///   tag: [u8; 2],
+/// }
+///
+/// let mut buf = [1, 2, b'o', b'k'];
+/// Header::swap_endian_in_buffer(&mut buf);
+/// assert_eq!([2, 1, b'o', b'k'], buf);
+/// ```
+pub trait SwapEndian: PackedSize {
+    /// Flip byte order of packed `Self` stored in `bytes`.
+    ///
+    /// # Panics
+    ///
+    /// Panic if [PackedSize](PackedSize) represents a different size than `bytes` slice.
+    fn swap_endian_in_buffer(bytes: &mut [u8]);
+}
+
+/// Byte order marker selecting between [EncodeLE](EncodeLE) / [EncodeBE](EncodeBE) and
+/// [DecodeLE](DecodeLE) / [DecodeBE](DecodeBE) in [Encode](Encode) and [Decode](Decode).
+pub trait ByteOrder {
+    /// Pack `value` into `bytes` using this byte order and return the number of bytes written.
+    fn encode<T: EncodeLE + EncodeBE + ?Sized>(value: &T, bytes: &mut [u8]) -> usize;
+
+    /// Create `T` from `bytes` packed using this byte order.
+    fn decode<T: DecodeLE + DecodeBE>(bytes: &[u8]) -> T;
+}
+
+/// Little-endian [ByteOrder](ByteOrder).
+pub enum LittleEndian {}
+
+impl ByteOrder for LittleEndian {
+    #[inline]
+    fn encode<T: EncodeLE + EncodeBE + ?Sized>(value: &T, bytes: &mut [u8]) -> usize {
+        value.encode_as_le_bytes(bytes)
+    }
+
+    #[inline]
+    fn decode<T: DecodeLE + DecodeBE>(bytes: &[u8]) -> T {
+        T::decode_from_le_bytes(bytes)
+    }
+}
+
+/// Big-endian [ByteOrder](ByteOrder).
+pub enum BigEndian {}
+
+impl ByteOrder for BigEndian {
+    #[inline]
+    fn encode<T: EncodeLE + EncodeBE + ?Sized>(value: &T, bytes: &mut [u8]) -> usize {
+        value.encode_as_be_bytes(bytes)
+    }
+
+    #[inline]
+    fn decode<T: DecodeLE + DecodeBE>(bytes: &[u8]) -> T {
+        T::decode_from_be_bytes(bytes)
+    }
+}
+
+/// [ByteOrder](ByteOrder) used by [encode_default](Encode::encode_default) and
+/// [decode_default](Decode::decode_default): [LittleEndian](LittleEndian), or
+/// [BigEndian](BigEndian) with the `default_be` feature.
+#[cfg(not(feature = "default_be"))]
+pub type DefaultByteOrder = LittleEndian;
+
+/// [ByteOrder](ByteOrder) used by [encode_default](Encode::encode_default) and
+/// [decode_default](Decode::decode_default): [LittleEndian](LittleEndian), or
+/// [BigEndian](BigEndian) with the `default_be` feature.
+#[cfg(feature = "default_be")]
+pub type DefaultByteOrder = BigEndian;
+
+/// Encode using byte order chosen by a [ByteOrder](ByteOrder) type parameter.
+///
+/// It's implemented for every type implementing both [EncodeLE](EncodeLE) and
+/// [EncodeBE](EncodeBE), so code can be written once for both byte orders.
+///
+/// ```rust
+/// use endian_codec::{BigEndian, ByteOrder, Encode, EncodeBE, EncodeLE, LittleEndian, PackedSize};
+///
+/// fn send<B: ByteOrder, T: Encode>(value: &T, buf: &mut [u8]) -> usize {
+///     value.encode_into::<B>(&mut buf[..T::PACKED_LEN])
+/// }
+///
+/// let mut buf = [0; 4];
+/// assert_eq!(2, send::<LittleEndian, _>(&0x0102u16, &mut buf));
+/// assert_eq!([2, 1, 0, 0], buf);
+/// assert_eq!(2, send::<BigEndian, _>(&0x0102u16, &mut buf));
+/// assert_eq!([1, 2, 0, 0], buf);
+/// ```
+pub trait Encode: EncodeLE + EncodeBE {
+    /// Borrow `self` and pack into `bytes` using `B` byte order. Returns the number of bytes
+    /// written.
+    ///
+    /// # Panics
+    ///
+    /// Panic if [PackedSize](PackedSize) represents a different size than `bytes` slice.
+    #[inline]
+    fn encode_into<B: ByteOrder>(&self, bytes: &mut [u8]) -> usize {
+        B::encode(self, bytes)
+    }
+
+    /// Like [encode_into](Encode::encode_into) with [DefaultByteOrder](DefaultByteOrder).
+    #[inline]
+    fn encode_default(&self, bytes: &mut [u8]) -> usize {
+        self.encode_into::<DefaultByteOrder>(bytes)
+    }
+}
+
+impl<T: EncodeLE + EncodeBE + ?Sized> Encode for T {}
+
+/// Decode using byte order chosen by a [ByteOrder](ByteOrder) type parameter.
+///
+/// It's implemented for every type implementing both [DecodeLE](DecodeLE) and
+/// [DecodeBE](DecodeBE).
+pub trait Decode: DecodeLE + DecodeBE + Sized {
+    /// Read `bytes` slice packed using `B` byte order and create `Self` from them.
+    ///
+    /// # Panics
+    ///
+    /// Panic if [PackedSize](PackedSize) represents a different size than `bytes` slice.
+    #[inline]
+    fn decode_from<B: ByteOrder>(bytes: &[u8]) -> Self {
+        B::decode(bytes)
+    }
+
+    /// Like [decode_from](Decode::decode_from) with [DefaultByteOrder](DefaultByteOrder).
+    #[inline]
+    fn decode_default(bytes: &[u8]) -> Self {
+        Self::decode_from::<DefaultByteOrder>(bytes)
+    }
+}
+
+impl<T: DecodeLE + DecodeBE> Decode for T {}
+
+/// Shorthand for types which can be both encoded and decoded as little-endian bytes.
+///
+/// It's implemented for every type implementing [EncodeLE](EncodeLE) and [DecodeLE](DecodeLE).
+///
+/// ```rust
+/// use endian_codec::CodecLE;
+///
+/// fn increment<T: CodecLE + Copy + From<u8> + core::ops::Add<Output = T>>(bytes: &mut [u8]) {
+///     let value = T::decode_from_le_bytes(bytes) + T::from(1);
+///     value.encode_as_le_bytes(bytes);
+/// }
+///
+/// let mut buf = [0xff, 0];
+/// increment::<u16>(&mut buf);
+/// assert_eq!([0, 1], buf);
+/// ```
+pub trait CodecLE: EncodeLE + DecodeLE {}
+
+impl<T: EncodeLE + DecodeLE> CodecLE for T {}
+
+/// Shorthand for types which can be both encoded and decoded as big-endian bytes.
+///
+/// It's implemented for every type implementing [EncodeBE](EncodeBE) and [DecodeBE](DecodeBE).
+pub trait CodecBE: EncodeBE + DecodeBE {}
+
+impl<T: EncodeBE + DecodeBE> CodecBE for T {}
+
+/// Shorthand for types which can be both encoded and decoded as mixed-endian bytes.
+///
+/// It's implemented for every type implementing [EncodeME](EncodeME) and [DecodeME](DecodeME).
+pub trait CodecME: EncodeME + DecodeME {}
+
+impl<T: EncodeME + DecodeME> CodecME for T {}
+
+/// Decode `T` from the beginning of `input` stored as a little-endian and advance `input` past
+/// the consumed bytes.
+///
+/// ```rust
+/// use endian_codec::decode_le_advance;
+///
+/// let mut input: &[u8] = &[1, 0, 2, 0, 0, 0, 3];
+/// let a: u16 = decode_le_advance(&mut input)?;
+/// let b: u32 = decode_le_advance(&mut input)?;
+/// assert_eq!((1, 2), (a, b));
+/// assert_eq!([3], input);
+/// # Ok::<(), endian_codec::EndianError>(())
+/// ```
+///
+/// # Errors
+///
+/// Return [BufferTooSmall](EndianError::BufferTooSmall) and leave `input` untouched if it
+/// holds fewer than `T::PACKED_LEN` bytes.
+pub fn decode_le_advance<T: DecodeLE>(input: &mut &[u8]) -> Result<T, EndianError> {
+    let bytes = split_advance::<T>(input)?;
+    Ok(T::decode_from_le_bytes(bytes))
+}
+
+/// Decode `T` from the beginning of `input` stored as a big-endian and advance `input` past the
+/// consumed bytes.
+///
+/// # Errors
+///
+/// Return [BufferTooSmall](EndianError::BufferTooSmall) and leave `input` untouched if it
+/// holds fewer than `T::PACKED_LEN` bytes.
+pub fn decode_be_advance<T: DecodeBE>(input: &mut &[u8]) -> Result<T, EndianError> {
+    let bytes = split_advance::<T>(input)?;
+    Ok(T::decode_from_be_bytes(bytes))
+}
+
+/// Decode `T` from the beginning of `input` stored as a mixed-endian and advance `input` past
+/// the consumed bytes.
+///
+/// # Errors
+///
+/// Return [BufferTooSmall](EndianError::BufferTooSmall) and leave `input` untouched if it
+/// holds fewer than `T::PACKED_LEN` bytes.
+pub fn decode_me_advance<T: DecodeME>(input: &mut &[u8]) -> Result<T, EndianError> {
+    let bytes = split_advance::<T>(input)?;
+    Ok(T::decode_from_me_bytes(bytes))
+}
+
+// Keeps items generated by the derive only if the `alloc` feature is enabled.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_alloc {
+    ($($item:tt)*) => { $($item)* };
+}
+
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_alloc {
+    ($($item:tt)*) => {};
+}
+
+/// Decode `T` from the first `T::PACKED_LEN` bytes of `bytes` stored as a little-endian and
+/// return it with the number of bytes consumed. Remaining bytes are ignored, which lets you peek
+/// at a header before parsing the rest of a frame.
+///
+/// ```rust
+/// use endian_codec::decode_prefix_le;
+///
+/// let frame = [2, 0, 0xAA, 0xBB];
+/// let (len, consumed): (u16, usize) = decode_prefix_le(&frame)?;
+/// assert_eq!((2, 2), (len, consumed));
+/// assert_eq!([0xAA, 0xBB], frame[consumed..]);
+/// # Ok::<(), endian_codec::EndianError>(())
+/// ```
+///
+/// # Errors
+///
+/// Return [BufferTooSmall](EndianError::BufferTooSmall) if `bytes` holds fewer than
+/// `T::PACKED_LEN` bytes.
+pub fn decode_prefix_le<T: DecodeLE>(bytes: &[u8]) -> Result<(T, usize), EndianError> {
+    let prefix = split_advance::<T>(&mut &*bytes)?;
+    Ok((T::decode_from_le_bytes(prefix), prefix.len()))
+}
+
+/// Decode `T` from the first `T::PACKED_LEN` bytes of `bytes` stored as a big-endian and return
+/// it with the number of bytes consumed.
+///
+/// # Errors
+///
+/// Return [BufferTooSmall](EndianError::BufferTooSmall) if `bytes` holds fewer than
+/// `T::PACKED_LEN` bytes.
+pub fn decode_prefix_be<T: DecodeBE>(bytes: &[u8]) -> Result<(T, usize), EndianError> {
+    let prefix = split_advance::<T>(&mut &*bytes)?;
+    Ok((T::decode_from_be_bytes(prefix), prefix.len()))
+}
+
+/// Decode `T` from the first `T::PACKED_LEN` bytes of `bytes` stored as a mixed-endian and
+/// return it with the number of bytes consumed.
+///
+/// # Errors
+///
+/// Return [BufferTooSmall](EndianError::BufferTooSmall) if `bytes` holds fewer than
+/// `T::PACKED_LEN` bytes.
+pub fn decode_prefix_me<T: DecodeME>(bytes: &[u8]) -> Result<(T, usize), EndianError> {
+    let prefix = split_advance::<T>(&mut &*bytes)?;
+    Ok((T::decode_from_me_bytes(prefix), prefix.len()))
+}
+
+fn split_advance<'a, T: PackedSize>(input: &mut &'a [u8]) -> Result<&'a [u8], EndianError> {
+    if input.len() < T::PACKED_LEN {
+        return Err(EndianError::BufferTooSmall {
+            needed: T::PACKED_LEN,
+            got: input.len(),
+        });
+    }
+    let (bytes, rest) = input.split_at(T::PACKED_LEN);
+    *input = rest;
+    Ok(bytes)
+}
+
+/// Iterator decoding consecutive records of `T` from a byte slice.
+///
+/// Created by [decode_iter_le](decode_iter_le), [decode_iter_be](decode_iter_be) and
+/// [decode_iter_me](decode_iter_me). It stops after the last full record, trailing bytes are
+/// available via [remainder](DecodedIter::remainder).
+pub struct DecodedIter<'a, T> {
+    chunks: ChunksExact<'a, u8>,
+    decode: fn(&[u8]) -> T,
+}
+
+impl<'a, T> DecodedIter<'a, T> {
+    /// Bytes left after the last full record.
+    pub fn remainder(&self) -> &'a [u8] {
+        self.chunks.remainder()
+    }
+
+    /// Decode all remaining records into slots taken from `scratch` and return them.
+    ///
+    /// # Errors
+    ///
+    /// Return [ScratchTooSmall](EndianError::ScratchTooSmall) if `scratch` has fewer free slots
+    /// than there are records. Nothing is taken from `scratch` then.
+    pub fn collect_into<'s>(
+        self,
+        scratch: &mut Scratch<'s, T>,
+    ) -> Result<&'s mut [T], EndianError> {
+        let needed = self.len();
+        let slots = scratch.alloc(needed).ok_or(EndianError::ScratchTooSmall {
+            needed,
+            got: scratch.remaining(),
+        })?;
+        fill_from_iter(self, slots);
+        Ok(slots)
+    }
+}
+
+impl<'a, T> Iterator for DecodedIter<'a, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.chunks.next().map(self.decode)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for DecodedIter<'a, T> {}
+
+impl<'a, T> FusedIterator for DecodedIter<'a, T> {}
+
+/// Decode consecutive records of `T` stored as a little-endian in `bytes`.
+///
+/// ```rust
+/// let values: Vec<u16> = endian_codec::decode_iter_le(&[1, 0, 2, 0, 3]).collect();
+/// assert_eq!(vec![1, 2], values);
+/// ```
+///
+/// # Panics
+///
+/// Panic if `T::PACKED_LEN` is 0.
+pub fn decode_iter_le<T: DecodeLE>(bytes: &[u8]) -> DecodedIter<'_, T> {
+    DecodedIter {
+        chunks: bytes.chunks_exact(T::PACKED_LEN),
+        decode: T::decode_from_le_bytes,
+    }
+}
+
+/// Decode consecutive records of `T` stored as a big-endian in `bytes`.
+///
+/// # Panics
+///
+/// Panic if `T::PACKED_LEN` is 0.
+pub fn decode_iter_be<T: DecodeBE>(bytes: &[u8]) -> DecodedIter<'_, T> {
+    DecodedIter {
+        chunks: bytes.chunks_exact(T::PACKED_LEN),
+        decode: T::decode_from_be_bytes,
+    }
+}
+
+/// Decode consecutive records of `T` stored as a mixed-endian in `bytes`.
+///
+/// # Panics
+///
+/// Panic if `T::PACKED_LEN` is 0.
+pub fn decode_iter_me<T: DecodeME>(bytes: &[u8]) -> DecodedIter<'_, T> {
+    DecodedIter {
+        chunks: bytes.chunks_exact(T::PACKED_LEN),
+        decode: T::decode_from_me_bytes,
+    }
+}
+
+/// Write a length prefix of type `L` stored as a little-endian, followed by the body written by
+/// `body` into the rest of `bytes`. `body` returns the number of bytes it wrote, which is then
+/// patched into the prefix. Returns the number of bytes written including the prefix.
+///
+/// ```rust
+/// use endian_codec::{encode_length_prefixed_le, EncodeLE};
+///
+/// let mut frame = [0; 16];
+/// let len = encode_length_prefixed_le::<u16, _>(&mut frame, |body| {
+///     0xAAu8.encode_as_le_bytes(&mut body[..1]) + 0xBBCCu16.encode_as_le_bytes(&mut body[1..3])
+/// });
+/// assert_eq!([3, 0, 0xAA, 0xCC, 0xBB], frame[..len]);
+/// ```
+///
+/// # Panics
+///
+/// Panic if `bytes` is shorter than the prefix or the body length doesn't fit into `L`.
+pub fn encode_length_prefixed_le<L, F>(bytes: &mut [u8], body: F) -> usize
+where
+    L: EncodeLE + TryFrom<usize>,
+    F: FnOnce(&mut [u8]) -> usize,
+{
+    let (prefix, rest) = bytes.split_at_mut(L::PACKED_LEN);
+    let len = body(rest);
+    let value = L::try_from(len)
+        .unwrap_or_else(|_| panic!("body length {} doesn't fit into the prefix", len));
+    value.encode_as_le_bytes(prefix) + len
+}
+
+/// Write a length prefix of type `L` stored as a big-endian, followed by the body written by
+/// `body` into the rest of `bytes`. `body` returns the number of bytes it wrote, which is then
+/// patched into the prefix. Returns the number of bytes written including the prefix.
+///
+/// # Panics
+///
+/// Panic if `bytes` is shorter than the prefix or the body length doesn't fit into `L`.
+pub fn encode_length_prefixed_be<L, F>(bytes: &mut [u8], body: F) -> usize
+where
+    L: EncodeBE + TryFrom<usize>,
+    F: FnOnce(&mut [u8]) -> usize,
+{
+    let (prefix, rest) = bytes.split_at_mut(L::PACKED_LEN);
+    let len = body(rest);
+    let value = L::try_from(len)
+        .unwrap_or_else(|_| panic!("body length {} doesn't fit into the prefix", len));
+    value.encode_as_be_bytes(prefix) + len
+}
+
+/// Decode header `H` stored as a little-endian at the start of `bytes` and return it with an
+/// iterator lazily decoding the records of `T` following it, borrowing `bytes` without allocating.
+///
+/// ```rust
+/// let frame = [2, 0, 10, 0, 0, 0, 20, 0, 0, 0];
+/// let (count, records) = endian_codec::decode_trailing_le::<u16, u32>(&frame)?;
+/// assert_eq!(2, count);
+/// assert_eq!(30, records.sum::<u32>());
+/// # Ok::<(), endian_codec::EndianError>(())
+/// ```
+///
+/// # Errors
+///
+/// Return [BufferTooSmall](EndianError::BufferTooSmall) if `bytes` holds fewer than
+/// `H::PACKED_LEN` bytes.
+///
+/// # Panics
+///
+/// Panic if `T::PACKED_LEN` is 0.
+pub fn decode_trailing_le<H: DecodeLE, T: DecodeLE>(
+    bytes: &[u8],
+) -> Result<(H, DecodedIter<'_, T>), EndianError> {
+    let (header, consumed) = decode_prefix_le(bytes)?;
+    Ok((header, decode_iter_le(&bytes[consumed..])))
+}
+
+/// Number of bytes taken by header `H` followed by `count` records of `T`, as read by
+/// [decode_trailing_le](decode_trailing_le) and friends. Use it to size a buffer exactly.
+///
+/// ```rust
+/// // `u32` count followed by four `u16` records
+/// const LEN: usize = endian_codec::trailing_len::<u32, u16>(4);
+/// assert_eq!(12, LEN);
+/// ```
+///
+/// # Panics
+///
+/// Panic if the length overflows `usize`.
+pub const fn trailing_len<H: PackedSize, T: PackedSize>(count: usize) -> usize {
+    match count.checked_mul(T::PACKED_LEN) {
+        Some(records) => field_end(H::PACKED_LEN, records),
+        None => panic!("field offset overflows usize"),
+    }
+}
+
+/// Return `len` if it equals `T::PACKED_LEN`, panic otherwise. Evaluated in a const context,
+/// e.g. as an array length, a hardcoded buffer size which doesn't match fails to compile.
+///
+/// ```rust
+/// use endian_codec::{check_len, PackedSize};
+///
+/// #[derive(PackedSize)]
+/// struct Version {
+///   major: u16,
+///   minor: u16,
+///   patch: u16,
+/// }
+///
+/// let buf = [0u8; check_len::<Version>(6)];
+/// # assert_eq!(6, buf.len());
+/// ```
+///
+/// ```compile_fail
+/// let buf = [0u8; endian_codec::check_len::<u32>(2)];
+/// ```
+pub const fn check_len<T: PackedSize>(len: usize) -> usize {
+    if len != T::PACKED_LEN {
+        panic!("length differs from PACKED_LEN");
+    }
+    len
+}
+
+/// Decode header `H` stored as a big-endian at the start of `bytes` and return it with an
+/// iterator lazily decoding the records of `T` following it.
+///
+/// # Errors
+///
+/// Return [BufferTooSmall](EndianError::BufferTooSmall) if `bytes` holds fewer than
+/// `H::PACKED_LEN` bytes.
+///
+/// # Panics
+///
+/// Panic if `T::PACKED_LEN` is 0.
+pub fn decode_trailing_be<H: DecodeBE, T: DecodeBE>(
+    bytes: &[u8],
+) -> Result<(H, DecodedIter<'_, T>), EndianError> {
+    let (header, consumed) = decode_prefix_be(bytes)?;
+    Ok((header, decode_iter_be(&bytes[consumed..])))
+}
+
+/// Decode header `H` stored as a mixed-endian at the start of `bytes` and return it with an
+/// iterator lazily decoding the records of `T` following it.
+///
+/// # Errors
+///
+/// Return [BufferTooSmall](EndianError::BufferTooSmall) if `bytes` holds fewer than
+/// `H::PACKED_LEN` bytes.
+///
+/// # Panics
+///
+/// Panic if `T::PACKED_LEN` is 0.
+pub fn decode_trailing_me<H: DecodeME, T: DecodeME>(
+    bytes: &[u8],
+) -> Result<(H, DecodedIter<'_, T>), EndianError> {
+    let (header, consumed) = decode_prefix_me(bytes)?;
+    Ok((header, decode_iter_me(&bytes[consumed..])))
+}
+
+/// Decode as many whole records of `T` stored as a little-endian in `bytes` as fit into `out`
+/// and return how many were written.
+///
+/// ```rust
+/// let mut out = [0u16; 4];
+/// let count = endian_codec::decode_slice_le_into(&[1, 0, 2, 0, 3], &mut out);
+/// assert_eq!(2, count);
+/// assert_eq!([1, 2, 0, 0], out);
+/// ```
+///
+/// # Panics
+///
+/// Panic if `T::PACKED_LEN` is 0.
+pub fn decode_slice_le_into<T: DecodeLE>(bytes: &[u8], out: &mut [T]) -> usize {
+    fill_from_iter(decode_iter_le(bytes), out)
+}
+
+/// Decode as many whole records of `T` stored as a big-endian in `bytes` as fit into `out`
+/// and return how many were written.
+///
+/// # Panics
+///
+/// Panic if `T::PACKED_LEN` is 0.
+pub fn decode_slice_be_into<T: DecodeBE>(bytes: &[u8], out: &mut [T]) -> usize {
+    fill_from_iter(decode_iter_be(bytes), out)
+}
+
+/// Decode as many whole records of `T` stored as a mixed-endian in `bytes` as fit into `out`
+/// and return how many were written.
+///
+/// # Panics
+///
+/// Panic if `T::PACKED_LEN` is 0.
+pub fn decode_slice_me_into<T: DecodeME>(bytes: &[u8], out: &mut [T]) -> usize {
+    fill_from_iter(decode_iter_me(bytes), out)
+}
+
+fn fill_from_iter<T>(iter: DecodedIter<'_, T>, out: &mut [T]) -> usize {
+    let mut count = 0;
+    for (slot, value) in out.iter_mut().zip(iter) {
+        *slot = value;
+        count += 1;
+    }
+    count
+}
+
+/// Decode from bytes stored as a little-endian by an older version of the struct.
+///
+/// Fields marked with `#[endian_codec(since = N)]` are present in the layout from version `N`
+/// onward. Decoding an older layout skips them and sets them to `Default::default()`.
+///
+/// ```rust
+/// use endian_codec::{PackedSize, DecodeLE, DecodeLEVersioned, DecodedMeta};
+///
+/// #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE, DecodeLEVersioned)]
+/// struct Status {
+///   code: u16,
+///   #[endian_codec(since = 2)]
+///   flags: u8,
+/// }
+///
+/// let (status, meta) = Status::decode_le_versioned(&[0x2A, 0x00], 1);
+/// assert_eq!(status, Status { code: 42, flags: 0 });
+/// assert_eq!(meta.defaulted, ["flags"]);
+/// ```
+pub trait DecodeLEVersioned: DecodeLE + Sized {
+    /// Read `bytes` slice packed as little-endian bytes by `version` of the layout and create
+    /// `Self` from them.
+    ///
+    /// # Panics
+    ///
+    /// Panic if `bytes` slice has a different size than the `version` layout.
+    fn decode_le_versioned(bytes: &[u8], version: u32) -> (Self, DecodedMeta);
+}
+
+/// Describes what [DecodeLEVersioned](DecodeLEVersioned) had to fill in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodedMeta {
+    /// Names of fields missing in the decoded layout and set to `Default::default()`.
+    /// Fields of tuple structs are named by their index.
+    pub defaulted: &'static [&'static str],
+}
+
+/// Describes where fields of a struct are placed in its packed bytes.
+///
+/// Derive it to check a wire layout while debugging. Endianness of a field comes from its
+/// `#[endian = "..."]` attribute or a struct-level `#[endian(..)]` default.
+///
+/// ```rust
+/// use endian_codec::{DebugLayout, Endian, PackedSize};
+///
+/// #[derive(PackedSize, DebugLayout)]
+/// struct Header {
+///   #[endian = "be"]
+///   magic: u32,
+///   len: u16,
+/// }
+///
+/// assert_eq!(Header::FIELDS[1].offset, 4);
+/// assert_eq!(Header::FIELDS[0].endian, Some(Endian::Big));
+/// ```
+pub trait DebugLayout: PackedSize {
+    /// Fields in the order they are packed.
+    const FIELDS: &'static [FieldLayout];
+
+    /// The same layout as plain `(name, offset, size, endian)` tuples, e.g. to feed tools
+    /// generating parsers in other languages. `endian` is `"le"`, `"be"`, `"pdp"` or `"me"` if the
+    /// field type decides it.
+    ///
+    /// ```rust
+    /// use endian_codec::{DebugLayout, PackedSize};
+    ///
+    /// #[derive(PackedSize, DebugLayout)]
+    /// struct Header {
+    ///   #[endian = "be"]
+    ///   magic: u32,
+    ///   #[endian = "le"]
+    ///   len: u16,
+    /// }
+    ///
+    /// assert_eq!(Header::SCHEMA, [("magic", 0, 4, "be"), ("len", 4, 2, "le")]);
+    /// ```
+    const SCHEMA: &'static [(&'static str, usize, usize, &'static str)];
+
+    /// Describe every field on its own line with its name, offset, size and endianness.
+    #[cfg(feature = "std")]
+    fn debug_layout() -> std::string::String {
+        use core::fmt::Write;
+
+        let mut layout = std::format!("{} bytes\n", Self::PACKED_LEN);
+        for field in Self::FIELDS {
+            let endian = match field.endian {
+                Some(Endian::Little) => "le",
+                Some(Endian::Big) => "be",
+                None => "me",
+            };
+            // writing into a String never fails
+            let _ = writeln!(
+                layout,
+                "{}: offset {}, size {}, {}",
+                field.name, field.offset, field.size, endian
+            );
+        }
+        layout
+    }
+}
+
+/// Placement of a single field reported by [DebugLayout](DebugLayout).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldLayout {
+    /// Field name, fields of tuple structs are named by their index.
+    pub name: &'static str,
+    /// Offset of the first byte of the field.
+    pub offset: usize,
+    /// Number of bytes the field takes.
+    pub size: usize,
+    /// Byte order of the field, `None` if the field type decides it (mixed-endian).
+    pub endian: Option<Endian>,
+}
+
+// Used by the derive for `PACKED_LEN` of an enum with `#[endian_codec(tag(...))]`.
+#[doc(hidden)]
+pub const fn tag_packed_len<E, T: PackedSize>(_encode: fn(&E) -> T) -> usize {
+    T::PACKED_LEN
+}
+
+// Used by the `SwapEndian` derive for an enum with `#[endian_codec(tag(...))]`.
+#[doc(hidden)]
+pub fn swap_tag_endian_in_buffer<E, T: SwapEndian>(_encode: fn(&E) -> T, bytes: &mut [u8]) {
+    T::swap_endian_in_buffer(bytes)
+}
+
+/// Represents size of a struct as packed bytes.
+///
+/// At this moment all settings with [repr](https://doc.rust-lang.org/nomicon/other-reprs.html)
+/// attribute are ignored.
+///
+/// In other words if struct is marked as `repr(packed)` attribute, `std::mem::sizeof<T>()` should return the
+/// same value as <T as PackedSize>::PACKED_LEN.
+///
+/// The derive can guard the layout against accidentally reordered fields: with
+/// `#[endian_codec(assert_field_order("major", "minor"))]` on a struct it fails to compile
+/// unless the fields are declared exactly in that order. Similarly
+/// `#[endian_codec(assert_packed_len = 4)]` makes `PACKED_LEN` itself assert the expected
+/// length, so a buffer like `[0; Version::PACKED_LEN]` can't silently change its size.
+///
+/// `PACKED_LEN` always describes the current layout, so fields marked with
+/// `#[endian_codec(since = N)]` are counted too (see [DecodeLEVersioned](DecodeLEVersioned)).
+///
+/// ```
+/// // On a 64-bit machine, the size of struct A can be 16 bytes to make it more optimized for speed.
+/// // but `PACKED_LEN` must be set to 12 bytes.
+/// struct A {
+///   p: i32,
+///   v: i64,
+/// }
+/// ```
+///
+/// `ENDIAN_SENSITIVE` tells whether the byte order changes the encoding at all. It's `false` for
+/// types made only of single bytes (e.g. `[u8; N]`), which generic code may then copy as they are.
+/// The derive sets it if any field is endian-sensitive; by default it's set for types longer than
+/// one byte.
+///
+/// `ZERO_NICHE` tells that all-zero bytes never hold a valid value, as for `NonZeroU32`.
+/// `Option<Self>` then stores `None` as zeros and takes no presence byte. Set it only if zeros
+/// can't be the encoding of any value, otherwise such a `Some` would decode as `None`.
+///
+/// ```
+/// use endian_codec::PackedSize;
+///
+/// assert!(!<[u8; 16]>::ENDIAN_SENSITIVE);
+/// assert!(<(u8, u16)>::ENDIAN_SENSITIVE);
+/// ```
+pub trait PackedSize {
+    const PACKED_LEN: usize;
+    const ENDIAN_SENSITIVE: bool = Self::PACKED_LEN > 1;
+    const ZERO_NICHE: bool = false;
+}
+
+macro_rules! impl_codec_for_primitives {
+    ($type:ty, $byte_len:expr) => {
+        impl PackedSize for $type {
+            const PACKED_LEN: usize = $byte_len;
+        }
+
+        impl EncodeLE for $type {
+            #[inline]
+            fn encode_as_le_bytes(&self, bytes: &mut [u8]) -> usize {
+                bytes.copy_from_slice(&(self.to_le_bytes()));
+                $byte_len
+            }
+        }
+
+        impl EncodeBE for $type {
+            #[inline]
+            fn encode_as_be_bytes(&self, bytes: &mut [u8]) -> usize {
+                bytes.copy_from_slice(&(self.to_be_bytes()));
+                $byte_len
+            }
+        }
+
+        impl EncodeNE for $type {
+            #[inline]
+            fn encode_as_ne_bytes(&self, bytes: &mut [u8]) -> usize {
+                bytes.copy_from_slice(&(self.to_ne_bytes()));
+                $byte_len
+            }
+        }
+
+        impl DecodeLE for $type {
+            #[inline]
+            fn decode_from_le_bytes(bytes: &[u8]) -> Self {
+                let mut arr = [0; $byte_len];
+                arr.copy_from_slice(&bytes);
+                Self::from_le_bytes(arr)
+            }
+        }
+
+        impl DecodeNE for $type {
+            #[inline]
+            fn decode_from_ne_bytes(bytes: &[u8]) -> Self {
+                let mut arr = [0; $byte_len];
+                arr.copy_from_slice(&bytes);
+                Self::from_ne_bytes(arr)
+            }
+        }
+
+        impl DecodeBE for $type {
+            #[inline]
+            fn decode_from_be_bytes(bytes: &[u8]) -> Self {
+                let mut arr = [0; $byte_len];
+                arr.copy_from_slice(&bytes);
+                Self::from_be_bytes(arr)
+            }
+        }
+
+        impl HashLE for $type {
+            #[inline]
+            fn hash_le<H: Hasher>(&self, state: &mut H) {
+                state.write(&self.to_le_bytes())
+            }
+        }
+
+        impl SwapEndian for $type {
+            #[inline]
+            fn swap_endian_in_buffer(bytes: &mut [u8]) {
+                assert_eq!($byte_len, bytes.len());
+                bytes.reverse()
+            }
+        }
+    };
+}
+
+impl_codec_for_primitives!(u8, 1);
 impl_codec_for_primitives!(i8, 1);
 
-impl EncodeME for u8 {
-    #[inline]
-    fn encode_as_me_bytes(&self, bytes: &mut [u8]) {
-        bytes.copy_from_slice(&(self.to_be_bytes()));
+impl EncodeME for u8 {
+    #[inline]
+    fn encode_as_me_bytes(&self, bytes: &mut [u8]) -> usize {
+        bytes.copy_from_slice(&(self.to_be_bytes()));
+        Self::PACKED_LEN
+    }
+}
+
+impl DecodeME for u8 {
+    #[inline]
+    fn decode_from_me_bytes(bytes: &[u8]) -> Self {
+        let mut arr = [0; 1];
+        arr.copy_from_slice(bytes);
+        Self::from_le_bytes(arr)
+    }
+}
+
+impl_codec_for_primitives!(u16, 2);
+impl_codec_for_primitives!(i16, 2);
+impl_codec_for_primitives!(u32, 4);
+impl_codec_for_primitives!(i32, 4);
+impl_codec_for_primitives!(u64, 8);
+impl_codec_for_primitives!(i64, 8);
+impl_codec_for_primitives!(u128, 16);
+impl_codec_for_primitives!(i128, 16);
+// floats go through their raw bytes, so NaN payloads are kept
+impl_codec_for_primitives!(f32, 4);
+impl_codec_for_primitives!(f64, 8);
+
+/// Stored as a single byte: `true` as `1` and `false` as `0`. Any non-zero byte decodes as `true`.
+impl PackedSize for bool {
+    const PACKED_LEN: usize = 1;
+}
+
+impl EncodeLE for bool {
+    #[inline]
+    fn encode_as_le_bytes(&self, bytes: &mut [u8]) -> usize {
+        u8::from(*self).encode_as_le_bytes(bytes)
+    }
+}
+
+impl EncodeBE for bool {
+    #[inline]
+    fn encode_as_be_bytes(&self, bytes: &mut [u8]) -> usize {
+        u8::from(*self).encode_as_be_bytes(bytes)
+    }
+}
+
+impl EncodeME for bool {
+    #[inline]
+    fn encode_as_me_bytes(&self, bytes: &mut [u8]) -> usize {
+        u8::from(*self).encode_as_me_bytes(bytes)
+    }
+}
+
+impl DecodeLE for bool {
+    #[inline]
+    fn decode_from_le_bytes(bytes: &[u8]) -> Self {
+        u8::decode_from_le_bytes(bytes) != 0
+    }
+}
+
+impl DecodeBE for bool {
+    #[inline]
+    fn decode_from_be_bytes(bytes: &[u8]) -> Self {
+        u8::decode_from_be_bytes(bytes) != 0
+    }
+}
+
+impl DecodeME for bool {
+    #[inline]
+    fn decode_from_me_bytes(bytes: &[u8]) -> Self {
+        u8::decode_from_me_bytes(bytes) != 0
+    }
+}
+
+impl HashLE for bool {
+    #[inline]
+    fn hash_le<H: Hasher>(&self, state: &mut H) {
+        u8::from(*self).hash_le(state)
+    }
+}
+
+impl SwapEndian for bool {
+    #[inline]
+    fn swap_endian_in_buffer(bytes: &mut [u8]) {
+        u8::swap_endian_in_buffer(bytes)
+    }
+}
+
+// `usize` and `isize` are always stored as 8 bytes (`u64`/`i64`), so the layout doesn't depend on
+// the target. Decoding a value which doesn't fit into the pointer width fails.
+macro_rules! impl_codec_for_pointer_sized {
+    ($type:ty, $wide:ty) => {
+        impl PackedSize for $type {
+            const PACKED_LEN: usize = 8;
+        }
+
+        impl EncodeLE for $type {
+            #[inline]
+            fn encode_as_le_bytes(&self, bytes: &mut [u8]) -> usize {
+                (*self as $wide).encode_as_le_bytes(bytes)
+            }
+        }
+
+        impl EncodeBE for $type {
+            #[inline]
+            fn encode_as_be_bytes(&self, bytes: &mut [u8]) -> usize {
+                (*self as $wide).encode_as_be_bytes(bytes)
+            }
+        }
+
+        impl DecodeLE for $type {
+            /// # Panics
+            ///
+            /// Panic if the value doesn't fit into the pointer width of the target.
+            #[inline]
+            fn decode_from_le_bytes(bytes: &[u8]) -> Self {
+                Self::try_from(<$wide>::decode_from_le_bytes(bytes))
+                    .expect("value overflows the pointer width")
+            }
+
+            #[inline]
+            fn try_decode_from_le_bytes(bytes: &[u8]) -> Result<Self, EndianError> {
+                check_decode_len(bytes.len(), Self::PACKED_LEN)?;
+                Self::try_from(<$wide>::decode_from_le_bytes(bytes))
+                    .map_err(|_| EndianError::InvalidValue)
+            }
+        }
+
+        impl DecodeBE for $type {
+            /// # Panics
+            ///
+            /// Panic if the value doesn't fit into the pointer width of the target.
+            #[inline]
+            fn decode_from_be_bytes(bytes: &[u8]) -> Self {
+                Self::try_from(<$wide>::decode_from_be_bytes(bytes))
+                    .expect("value overflows the pointer width")
+            }
+
+            #[inline]
+            fn try_decode_from_be_bytes(bytes: &[u8]) -> Result<Self, EndianError> {
+                check_decode_len(bytes.len(), Self::PACKED_LEN)?;
+                Self::try_from(<$wide>::decode_from_be_bytes(bytes))
+                    .map_err(|_| EndianError::InvalidValue)
+            }
+        }
+
+        impl HashLE for $type {
+            #[inline]
+            fn hash_le<H: Hasher>(&self, state: &mut H) {
+                (*self as $wide).hash_le(state)
+            }
+        }
+
+        impl SwapEndian for $type {
+            #[inline]
+            fn swap_endian_in_buffer(bytes: &mut [u8]) {
+                <$wide>::swap_endian_in_buffer(bytes)
+            }
+        }
+    };
+}
+
+impl_codec_for_pointer_sized!(usize, u64);
+impl_codec_for_pointer_sized!(isize, i64);
+
+// `char` is stored as its `u32` Unicode scalar value.
+impl PackedSize for char {
+    const PACKED_LEN: usize = 4;
+}
+
+impl EncodeLE for char {
+    #[inline]
+    fn encode_as_le_bytes(&self, bytes: &mut [u8]) -> usize {
+        u32::from(*self).encode_as_le_bytes(bytes)
+    }
+}
+
+impl EncodeBE for char {
+    #[inline]
+    fn encode_as_be_bytes(&self, bytes: &mut [u8]) -> usize {
+        u32::from(*self).encode_as_be_bytes(bytes)
+    }
+}
+
+fn char_from_u32(value: u32) -> Result<char, EndianError> {
+    char::from_u32(value).ok_or(EndianError::InvalidValue)
+}
+
+impl DecodeLE for char {
+    /// # Panics
+    ///
+    /// Panic if the value is not a Unicode scalar value.
+    #[inline]
+    fn decode_from_le_bytes(bytes: &[u8]) -> Self {
+        char_from_u32(u32::decode_from_le_bytes(bytes)).expect("invalid char value")
+    }
+
+    #[inline]
+    fn try_decode_from_le_bytes(bytes: &[u8]) -> Result<Self, EndianError> {
+        check_decode_len(bytes.len(), Self::PACKED_LEN)?;
+        char_from_u32(u32::decode_from_le_bytes(bytes))
+    }
+}
+
+impl DecodeBE for char {
+    /// # Panics
+    ///
+    /// Panic if the value is not a Unicode scalar value.
+    #[inline]
+    fn decode_from_be_bytes(bytes: &[u8]) -> Self {
+        char_from_u32(u32::decode_from_be_bytes(bytes)).expect("invalid char value")
+    }
+
+    #[inline]
+    fn try_decode_from_be_bytes(bytes: &[u8]) -> Result<Self, EndianError> {
+        check_decode_len(bytes.len(), Self::PACKED_LEN)?;
+        char_from_u32(u32::decode_from_be_bytes(bytes))
+    }
+}
+
+impl HashLE for char {
+    #[inline]
+    fn hash_le<H: Hasher>(&self, state: &mut H) {
+        u32::from(*self).hash_le(state)
+    }
+}
+
+impl SwapEndian for char {
+    #[inline]
+    fn swap_endian_in_buffer(bytes: &mut [u8]) {
+        u32::swap_endian_in_buffer(bytes)
+    }
+}
+
+// `Ordering` is stored as a signed byte: `Less = -1`, `Equal = 0`, `Greater = 1`.
+impl PackedSize for Ordering {
+    const PACKED_LEN: usize = 1;
+}
+
+impl EncodeLE for Ordering {
+    #[inline]
+    fn encode_as_le_bytes(&self, bytes: &mut [u8]) -> usize {
+        (*self as i8).encode_as_le_bytes(bytes)
+    }
+}
+
+impl EncodeBE for Ordering {
+    #[inline]
+    fn encode_as_be_bytes(&self, bytes: &mut [u8]) -> usize {
+        (*self as i8).encode_as_be_bytes(bytes)
+    }
+}
+
+impl EncodeME for Ordering {
+    #[inline]
+    fn encode_as_me_bytes(&self, bytes: &mut [u8]) -> usize {
+        (*self as i8).encode_as_le_bytes(bytes)
+    }
+}
+
+fn ordering_from_i8(value: i8) -> Result<Ordering, EndianError> {
+    match value {
+        -1 => Ok(Ordering::Less),
+        0 => Ok(Ordering::Equal),
+        1 => Ok(Ordering::Greater),
+        _ => Err(EndianError::InvalidValue),
+    }
+}
+
+fn expect_ordering(value: i8) -> Ordering {
+    ordering_from_i8(value).unwrap_or_else(|_| panic!("invalid Ordering value: {}", value))
+}
+
+impl DecodeLE for Ordering {
+    /// # Panics
+    ///
+    /// Panic if the value is not `-1`, `0` or `1`.
+    #[inline]
+    fn decode_from_le_bytes(bytes: &[u8]) -> Self {
+        expect_ordering(i8::decode_from_le_bytes(bytes))
+    }
+
+    #[inline]
+    fn try_decode_from_le_bytes(bytes: &[u8]) -> Result<Self, EndianError> {
+        check_decode_len(bytes.len(), Self::PACKED_LEN)?;
+        ordering_from_i8(i8::decode_from_le_bytes(bytes))
+    }
+}
+
+impl DecodeBE for Ordering {
+    /// # Panics
+    ///
+    /// Panic if the value is not `-1`, `0` or `1`.
+    #[inline]
+    fn decode_from_be_bytes(bytes: &[u8]) -> Self {
+        expect_ordering(i8::decode_from_be_bytes(bytes))
+    }
+
+    #[inline]
+    fn try_decode_from_be_bytes(bytes: &[u8]) -> Result<Self, EndianError> {
+        check_decode_len(bytes.len(), Self::PACKED_LEN)?;
+        ordering_from_i8(i8::decode_from_be_bytes(bytes))
+    }
+}
+
+impl DecodeME for Ordering {
+    /// # Panics
+    ///
+    /// Panic if the value is not `-1`, `0` or `1`.
+    #[inline]
+    fn decode_from_me_bytes(bytes: &[u8]) -> Self {
+        expect_ordering(i8::decode_from_le_bytes(bytes))
+    }
+
+    #[inline]
+    fn try_decode_from_me_bytes(bytes: &[u8]) -> Result<Self, EndianError> {
+        check_decode_len(bytes.len(), Self::PACKED_LEN)?;
+        ordering_from_i8(i8::decode_from_le_bytes(bytes))
+    }
+}
+
+impl HashLE for Ordering {
+    #[inline]
+    fn hash_le<H: Hasher>(&self, state: &mut H) {
+        (*self as i8).hash_le(state)
+    }
+}
+
+impl SwapEndian for Ordering {
+    #[inline]
+    fn swap_endian_in_buffer(bytes: &mut [u8]) {
+        i8::swap_endian_in_buffer(bytes)
+    }
+}
+
+// IP address is always stored in network order (big-endian), no matter the chosen endianness.
+impl PackedSize for Ipv4Addr {
+    const PACKED_LEN: usize = 4;
+}
+
+impl EncodeLE for Ipv4Addr {
+    #[inline]
+    fn encode_as_le_bytes(&self, bytes: &mut [u8]) -> usize {
+        bytes.copy_from_slice(&self.octets());
+        Self::PACKED_LEN
+    }
+}
+
+impl EncodeBE for Ipv4Addr {
+    #[inline]
+    fn encode_as_be_bytes(&self, bytes: &mut [u8]) -> usize {
+        bytes.copy_from_slice(&self.octets());
+        Self::PACKED_LEN
+    }
+}
+
+impl EncodeME for Ipv4Addr {
+    #[inline]
+    fn encode_as_me_bytes(&self, bytes: &mut [u8]) -> usize {
+        bytes.copy_from_slice(&self.octets());
+        Self::PACKED_LEN
+    }
+}
+
+impl DecodeLE for Ipv4Addr {
+    #[inline]
+    fn decode_from_le_bytes(bytes: &[u8]) -> Self {
+        Self::from(<[u8; 4]>::decode_from_le_bytes(bytes))
+    }
+}
+
+impl DecodeBE for Ipv4Addr {
+    #[inline]
+    fn decode_from_be_bytes(bytes: &[u8]) -> Self {
+        Self::from(<[u8; 4]>::decode_from_be_bytes(bytes))
+    }
+}
+
+impl DecodeME for Ipv4Addr {
+    #[inline]
+    fn decode_from_me_bytes(bytes: &[u8]) -> Self {
+        Self::from(<[u8; 4]>::decode_from_me_bytes(bytes))
+    }
+}
+
+impl HashLE for Ipv4Addr {
+    #[inline]
+    fn hash_le<H: Hasher>(&self, state: &mut H) {
+        state.write(&self.octets())
+    }
+}
+
+impl SwapEndian for Ipv4Addr {
+    #[inline]
+    fn swap_endian_in_buffer(bytes: &mut [u8]) {
+        assert_eq!(4, bytes.len());
+    }
+}
+
+// Socket address is an IP address (network order) followed by a port in the chosen endianness.
+impl PackedSize for SocketAddrV4 {
+    const PACKED_LEN: usize = Ipv4Addr::PACKED_LEN + u16::PACKED_LEN;
+}
+
+impl EncodeLE for SocketAddrV4 {
+    #[inline]
+    fn encode_as_le_bytes(&self, bytes: &mut [u8]) -> usize {
+        let (ip, port) = bytes.split_at_mut(Ipv4Addr::PACKED_LEN);
+        self.ip().encode_as_le_bytes(ip) + self.port().encode_as_le_bytes(port)
+    }
+}
+
+impl EncodeBE for SocketAddrV4 {
+    #[inline]
+    fn encode_as_be_bytes(&self, bytes: &mut [u8]) -> usize {
+        let (ip, port) = bytes.split_at_mut(Ipv4Addr::PACKED_LEN);
+        self.ip().encode_as_be_bytes(ip) + self.port().encode_as_be_bytes(port)
+    }
+}
+
+impl DecodeLE for SocketAddrV4 {
+    #[inline]
+    fn decode_from_le_bytes(bytes: &[u8]) -> Self {
+        let (ip, port) = bytes.split_at(Ipv4Addr::PACKED_LEN);
+        Self::new(
+            Ipv4Addr::decode_from_le_bytes(ip),
+            u16::decode_from_le_bytes(port),
+        )
+    }
+}
+
+impl DecodeBE for SocketAddrV4 {
+    #[inline]
+    fn decode_from_be_bytes(bytes: &[u8]) -> Self {
+        let (ip, port) = bytes.split_at(Ipv4Addr::PACKED_LEN);
+        Self::new(
+            Ipv4Addr::decode_from_be_bytes(ip),
+            u16::decode_from_be_bytes(port),
+        )
+    }
+}
+
+impl HashLE for SocketAddrV4 {
+    #[inline]
+    fn hash_le<H: Hasher>(&self, state: &mut H) {
+        self.ip().hash_le(state);
+        self.port().hash_le(state);
+    }
+}
+
+impl SwapEndian for SocketAddrV4 {
+    #[inline]
+    fn swap_endian_in_buffer(bytes: &mut [u8]) {
+        let (ip, port) = bytes.split_at_mut(Ipv4Addr::PACKED_LEN);
+        Ipv4Addr::swap_endian_in_buffer(ip);
+        u16::swap_endian_in_buffer(port);
+    }
+}
+
+// `Duration` is stored as `u64` seconds followed by `u32` nanoseconds. Nanoseconds over a second
+// are carried into the seconds on decode, which panics if the seconds overflow.
+impl PackedSize for Duration {
+    const PACKED_LEN: usize = u64::PACKED_LEN + u32::PACKED_LEN;
+}
+
+impl EncodeLE for Duration {
+    #[inline]
+    fn encode_as_le_bytes(&self, bytes: &mut [u8]) -> usize {
+        let (secs, nanos) = bytes.split_at_mut(u64::PACKED_LEN);
+        self.as_secs().encode_as_le_bytes(secs) + self.subsec_nanos().encode_as_le_bytes(nanos)
+    }
+}
+
+impl EncodeBE for Duration {
+    #[inline]
+    fn encode_as_be_bytes(&self, bytes: &mut [u8]) -> usize {
+        let (secs, nanos) = bytes.split_at_mut(u64::PACKED_LEN);
+        self.as_secs().encode_as_be_bytes(secs) + self.subsec_nanos().encode_as_be_bytes(nanos)
+    }
+}
+
+// `Duration::new` carries whole seconds out of `nanos`, which can overflow `secs`, so only
+// normalized durations are accepted.
+fn duration_from_parts(secs: u64, nanos: u32) -> Result<Duration, EndianError> {
+    if nanos >= 1_000_000_000 {
+        return Err(EndianError::InvalidValue);
+    }
+    Ok(Duration::new(secs, nanos))
+}
+
+impl DecodeLE for Duration {
+    /// # Panics
+    ///
+    /// Panic if the nanoseconds are `1_000_000_000` or more and carrying them into the seconds
+    /// overflows.
+    #[inline]
+    fn decode_from_le_bytes(bytes: &[u8]) -> Self {
+        let (secs, nanos) = bytes.split_at(u64::PACKED_LEN);
+        Self::new(
+            u64::decode_from_le_bytes(secs),
+            u32::decode_from_le_bytes(nanos),
+        )
+    }
+
+    /// Return [InvalidValue](EndianError::InvalidValue) if the nanoseconds are `1_000_000_000`
+    /// or more.
+    #[inline]
+    fn try_decode_from_le_bytes(bytes: &[u8]) -> Result<Self, EndianError> {
+        check_decode_len(bytes.len(), Self::PACKED_LEN)?;
+        let (secs, nanos) = bytes.split_at(u64::PACKED_LEN);
+        duration_from_parts(
+            u64::decode_from_le_bytes(secs),
+            u32::decode_from_le_bytes(nanos),
+        )
+    }
+}
+
+impl DecodeBE for Duration {
+    /// # Panics
+    ///
+    /// Panic if the nanoseconds are `1_000_000_000` or more and carrying them into the seconds
+    /// overflows.
+    #[inline]
+    fn decode_from_be_bytes(bytes: &[u8]) -> Self {
+        let (secs, nanos) = bytes.split_at(u64::PACKED_LEN);
+        Self::new(
+            u64::decode_from_be_bytes(secs),
+            u32::decode_from_be_bytes(nanos),
+        )
+    }
+
+    /// Return [InvalidValue](EndianError::InvalidValue) if the nanoseconds are `1_000_000_000`
+    /// or more.
+    #[inline]
+    fn try_decode_from_be_bytes(bytes: &[u8]) -> Result<Self, EndianError> {
+        check_decode_len(bytes.len(), Self::PACKED_LEN)?;
+        let (secs, nanos) = bytes.split_at(u64::PACKED_LEN);
+        duration_from_parts(
+            u64::decode_from_be_bytes(secs),
+            u32::decode_from_be_bytes(nanos),
+        )
+    }
+}
+
+impl HashLE for Duration {
+    #[inline]
+    fn hash_le<H: Hasher>(&self, state: &mut H) {
+        self.as_secs().hash_le(state);
+        self.subsec_nanos().hash_le(state);
+    }
+}
+
+impl SwapEndian for Duration {
+    #[inline]
+    fn swap_endian_in_buffer(bytes: &mut [u8]) {
+        let (secs, nanos) = bytes.split_at_mut(u64::PACKED_LEN);
+        u64::swap_endian_in_buffer(secs);
+        u32::swap_endian_in_buffer(nanos);
+    }
+}
+
+// Native-endian is the little- or big-endian layout, depending on the target.
+macro_rules! impl_codec_ne_for_target_endian {
+    ($($type:ty),+) => {
+        $(
+            impl EncodeNE for $type {
+                #[inline]
+                fn encode_as_ne_bytes(&self, bytes: &mut [u8]) -> usize {
+                    #[cfg(target_endian = "little")]
+                    return self.encode_as_le_bytes(bytes);
+                    #[cfg(target_endian = "big")]
+                    return self.encode_as_be_bytes(bytes);
+                }
+            }
+
+            impl DecodeNE for $type {
+                #[inline]
+                fn decode_from_ne_bytes(bytes: &[u8]) -> Self {
+                    #[cfg(target_endian = "little")]
+                    return Self::decode_from_le_bytes(bytes);
+                    #[cfg(target_endian = "big")]
+                    return Self::decode_from_be_bytes(bytes);
+                }
+
+                #[inline]
+                fn try_decode_from_ne_bytes(bytes: &[u8]) -> Result<Self, EndianError> {
+                    #[cfg(target_endian = "little")]
+                    return Self::try_decode_from_le_bytes(bytes);
+                    #[cfg(target_endian = "big")]
+                    return Self::try_decode_from_be_bytes(bytes);
+                }
+            }
+        )+
+    };
+}
+
+impl_codec_ne_for_target_endian!(
+    bool,
+    char,
+    usize,
+    isize,
+    Ordering,
+    Ipv4Addr,
+    SocketAddrV4,
+    Duration
+);
+
+// Zero-sized types take no bytes, so there is nothing to encode or decode.
+macro_rules! impl_codec_for_zero_sized {
+    ($type:ty, $value:expr $(, $gen:ident)*) => {
+        impl<$($gen: ?Sized),*> PackedSize for $type {
+            const PACKED_LEN: usize = 0;
+        }
+
+        impl<$($gen: ?Sized),*> EncodeBE for $type {
+            #[inline]
+            fn encode_as_be_bytes(&self, _bytes: &mut [u8]) -> usize {
+                0
+            }
+        }
+
+        impl<$($gen: ?Sized),*> EncodeLE for $type {
+            #[inline]
+            fn encode_as_le_bytes(&self, _bytes: &mut [u8]) -> usize {
+                0
+            }
+        }
+
+        impl<$($gen: ?Sized),*> EncodeME for $type {
+            #[inline]
+            fn encode_as_me_bytes(&self, _bytes: &mut [u8]) -> usize {
+                0
+            }
+        }
+
+        impl<$($gen: ?Sized),*> DecodeBE for $type {
+            #[inline]
+            fn decode_from_be_bytes(_bytes: &[u8]) -> Self {
+                $value
+            }
+        }
+
+        impl<$($gen: ?Sized),*> DecodeLE for $type {
+            #[inline]
+            fn decode_from_le_bytes(_bytes: &[u8]) -> Self {
+                $value
+            }
+        }
+
+        impl<$($gen: ?Sized),*> DecodeME for $type {
+            #[inline]
+            fn decode_from_me_bytes(_bytes: &[u8]) -> Self {
+                $value
+            }
+        }
+
+        impl<$($gen: ?Sized),*> EncodeNE for $type {
+            #[inline]
+            fn encode_as_ne_bytes(&self, _bytes: &mut [u8]) -> usize {
+                0
+            }
+        }
+
+        impl<$($gen: ?Sized),*> DecodeNE for $type {
+            #[inline]
+            fn decode_from_ne_bytes(_bytes: &[u8]) -> Self {
+                $value
+            }
+        }
+
+        impl<$($gen: ?Sized),*> HashLE for $type {
+            #[inline]
+            fn hash_le<H: Hasher>(&self, _state: &mut H) {}
+        }
+
+        impl<$($gen: ?Sized),*> SwapEndian for $type {
+            #[inline]
+            fn swap_endian_in_buffer(_bytes: &mut [u8]) {}
+        }
+    };
+}
+
+impl_codec_for_zero_sized!((), ());
+impl_codec_for_zero_sized!(PhantomData<T>, PhantomData, T);
+
+impl<T: PackedSize, const N: usize> PackedSize for [T; N] {
+    const PACKED_LEN: usize = N * T::PACKED_LEN;
+    const ENDIAN_SENSITIVE: bool = N > 0 && T::ENDIAN_SENSITIVE;
+}
+
+// Elements are encoded one after another, each in its own byte order.
+macro_rules! impl_codec_for_array {
+    ($($enc_trait:ident, $enc:ident, $dec_trait:ident, $dec:ident, $try_dec:ident);+) => {
+        $(
+            impl<T: $enc_trait, const N: usize> $enc_trait for [T; N] {
+                #[inline]
+                fn $enc(&self, bytes: &mut [u8]) -> usize {
+                    assert_eq!(Self::PACKED_LEN, bytes.len());
+                    for (i, item) in self.iter().enumerate() {
+                        item.$enc(&mut bytes[i * T::PACKED_LEN..(i + 1) * T::PACKED_LEN]);
+                    }
+                    Self::PACKED_LEN
+                }
+            }
+
+            impl<T: $dec_trait, const N: usize> $dec_trait for [T; N] {
+                #[inline]
+                fn $dec(bytes: &[u8]) -> Self {
+                    assert_eq!(Self::PACKED_LEN, bytes.len());
+                    core::array::from_fn(|i| {
+                        T::$dec(&bytes[i * T::PACKED_LEN..(i + 1) * T::PACKED_LEN])
+                    })
+                }
+
+                // every element is validated by its own `try_decode`
+                #[inline]
+                fn $try_dec(bytes: &[u8]) -> Result<Self, EndianError> {
+                    check_decode_len(bytes.len(), Self::PACKED_LEN)?;
+                    let mut error = None;
+                    let array = core::array::from_fn(|i| {
+                        match T::$try_dec(&bytes[i * T::PACKED_LEN..(i + 1) * T::PACKED_LEN]) {
+                            Ok(item) => Some(item),
+                            Err(err) => {
+                                error.get_or_insert(err);
+                                None
+                            }
+                        }
+                    });
+                    match error {
+                        Some(err) => Err(err),
+                        None => Ok(array.map(Option::unwrap)),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_codec_for_array!(
+    EncodeLE, encode_as_le_bytes, DecodeLE, decode_from_le_bytes, try_decode_from_le_bytes;
+    EncodeBE, encode_as_be_bytes, DecodeBE, decode_from_be_bytes, try_decode_from_be_bytes;
+    EncodeME, encode_as_me_bytes, DecodeME, decode_from_me_bytes, try_decode_from_me_bytes;
+    EncodeNE, encode_as_ne_bytes, DecodeNE, decode_from_ne_bytes, try_decode_from_ne_bytes
+);
+
+impl<T: HashLE, const N: usize> HashLE for [T; N] {
+    #[inline]
+    fn hash_le<H: Hasher>(&self, state: &mut H) {
+        for item in self {
+            item.hash_le(state);
+        }
+    }
+}
+
+impl<T: SwapEndian, const N: usize> SwapEndian for [T; N] {
+    #[inline]
+    fn swap_endian_in_buffer(bytes: &mut [u8]) {
+        assert_eq!(Self::PACKED_LEN, bytes.len());
+        if T::PACKED_LEN > 0 {
+            for bytes in bytes.chunks_exact_mut(T::PACKED_LEN) {
+                T::swap_endian_in_buffer(bytes);
+            }
+        }
+    }
+}
+
+// A presence byte (`0` or `1`) followed by the value. The value bytes are always reserved, so
+// `PACKED_LEN` stays fixed, and they're zeroed for `None`. Types with `ZERO_NICHE` store `None`
+// as zeros instead and need no presence byte.
+impl<T: PackedSize> PackedSize for Option<T> {
+    const PACKED_LEN: usize = if T::ZERO_NICHE {
+        T::PACKED_LEN
+    } else {
+        1 + T::PACKED_LEN
+    };
+    const ENDIAN_SENSITIVE: bool = T::ENDIAN_SENSITIVE;
+}
+
+fn is_zeroed(bytes: &[u8]) -> bool {
+    bytes.iter().all(|&byte| byte == 0)
+}
+
+macro_rules! impl_codec_for_option {
+    ($($enc_trait:ident, $enc:ident, $dec_trait:ident, $dec:ident, $try_dec:ident);+) => {
+        $(
+            impl<T: $enc_trait> $enc_trait for Option<T> {
+                #[inline]
+                fn $enc(&self, bytes: &mut [u8]) -> usize {
+                    assert_eq!(Self::PACKED_LEN, bytes.len());
+                    let value = if T::ZERO_NICHE {
+                        bytes
+                    } else {
+                        let (flag, value) = bytes.split_at_mut(1);
+                        flag[0] = u8::from(self.is_some());
+                        value
+                    };
+                    match self {
+                        Some(item) => {
+                            item.$enc(value);
+                        }
+                        None => value.fill(0),
+                    }
+                    Self::PACKED_LEN
+                }
+            }
+
+            impl<T: $dec_trait> $dec_trait for Option<T> {
+                #[inline]
+                fn $dec(bytes: &[u8]) -> Self {
+                    assert_eq!(Self::PACKED_LEN, bytes.len());
+                    if T::ZERO_NICHE {
+                        return match is_zeroed(bytes) {
+                            true => None,
+                            false => Some(T::$dec(bytes)),
+                        };
+                    }
+                    // like `bool`, any non-zero flag means present
+                    match bytes[0] {
+                        0 => None,
+                        _ => Some(T::$dec(&bytes[1..])),
+                    }
+                }
+
+                // the flag has to be `0` or `1` and a present value is validated by its own
+                // `try_decode`
+                #[inline]
+                fn $try_dec(bytes: &[u8]) -> Result<Self, EndianError> {
+                    check_decode_len(bytes.len(), Self::PACKED_LEN)?;
+                    if T::ZERO_NICHE {
+                        return match is_zeroed(bytes) {
+                            true => Ok(None),
+                            false => T::$try_dec(bytes).map(Some),
+                        };
+                    }
+                    match bytes[0] {
+                        0 => Ok(None),
+                        1 => T::$try_dec(&bytes[1..]).map(Some),
+                        _ => Err(EndianError::InvalidValue),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_codec_for_option!(
+    EncodeLE, encode_as_le_bytes, DecodeLE, decode_from_le_bytes, try_decode_from_le_bytes;
+    EncodeBE, encode_as_be_bytes, DecodeBE, decode_from_be_bytes, try_decode_from_be_bytes;
+    EncodeME, encode_as_me_bytes, DecodeME, decode_from_me_bytes, try_decode_from_me_bytes;
+    EncodeNE, encode_as_ne_bytes, DecodeNE, decode_from_ne_bytes, try_decode_from_ne_bytes
+);
+
+impl<T: HashLE + PackedSize> HashLE for Option<T> {
+    #[inline]
+    fn hash_le<H: Hasher>(&self, state: &mut H) {
+        if !T::ZERO_NICHE {
+            u8::from(self.is_some()).hash_le(state);
+        }
+        match self {
+            Some(item) => item.hash_le(state),
+            None => hash_zeros(state, T::PACKED_LEN),
+        }
+    }
+}
+
+// `NonZero*` types are stored as their inner integer. Zero is rejected on decode, which leaves
+// it free to store `None` of `Option<NonZero*>`.
+macro_rules! impl_codec_for_non_zero {
+    ($($type:ty, $int:ty);+) => {
+        $(
+            impl PackedSize for $type {
+                const PACKED_LEN: usize = <$int>::PACKED_LEN;
+                const ZERO_NICHE: bool = true;
+            }
+
+            impl_codec_for_non_zero!(@codec $type, $int,
+                EncodeLE, encode_as_le_bytes, DecodeLE, decode_from_le_bytes, try_decode_from_le_bytes;
+                EncodeBE, encode_as_be_bytes, DecodeBE, decode_from_be_bytes, try_decode_from_be_bytes;
+                EncodeNE, encode_as_ne_bytes, DecodeNE, decode_from_ne_bytes, try_decode_from_ne_bytes
+            );
+
+            impl HashLE for $type {
+                #[inline]
+                fn hash_le<H: Hasher>(&self, state: &mut H) {
+                    self.get().hash_le(state)
+                }
+            }
+
+            impl SwapEndian for $type {
+                #[inline]
+                fn swap_endian_in_buffer(bytes: &mut [u8]) {
+                    <$int>::swap_endian_in_buffer(bytes)
+                }
+            }
+        )+
+    };
+    (@codec $type:ty, $int:ty,
+     $($enc_trait:ident, $enc:ident, $dec_trait:ident, $dec:ident, $try_dec:ident);+) => {
+        $(
+            impl $enc_trait for $type {
+                #[inline]
+                fn $enc(&self, bytes: &mut [u8]) -> usize {
+                    self.get().$enc(bytes)
+                }
+            }
+
+            impl $dec_trait for $type {
+                /// # Panics
+                ///
+                /// Panic if the value is zero.
+                #[inline]
+                fn $dec(bytes: &[u8]) -> Self {
+                    <$type>::new(<$int>::$dec(bytes)).expect("zero NonZero value")
+                }
+
+                #[inline]
+                fn $try_dec(bytes: &[u8]) -> Result<Self, EndianError> {
+                    check_decode_len(bytes.len(), Self::PACKED_LEN)?;
+                    <$type>::new(<$int>::$dec(bytes)).ok_or(EndianError::InvalidValue)
+                }
+            }
+        )+
+    };
+}
+
+impl_codec_for_non_zero!(
+    NonZeroU8, u8;
+    NonZeroU16, u16;
+    NonZeroU32, u32;
+    NonZeroU64, u64;
+    NonZeroU128, u128;
+    NonZeroI8, i8;
+    NonZeroI16, i16;
+    NonZeroI32, i32;
+    NonZeroI64, i64;
+    NonZeroI128, i128
+);
+
+macro_rules! impl_codec_for_ref {
+    ($($trait:ident, $method:ident);+) => {
+        impl<T: PackedSize + ?Sized> PackedSize for &T {
+            const PACKED_LEN: usize = T::PACKED_LEN;
+            const ENDIAN_SENSITIVE: bool = T::ENDIAN_SENSITIVE;
+        }
+
+        $(
+            impl<T: $trait + ?Sized> $trait for &T {
+                #[inline]
+                fn $method(&self, bytes: &mut [u8]) -> usize {
+                    (**self).$method(bytes)
+                }
+            }
+        )+
+
+        impl<T: HashLE + ?Sized> HashLE for &T {
+            #[inline]
+            fn hash_le<H: Hasher>(&self, state: &mut H) {
+                (**self).hash_le(state)
+            }
+        }
+    };
+}
+
+impl_codec_for_ref!(
+    EncodeLE, encode_as_le_bytes;
+    EncodeBE, encode_as_be_bytes;
+    EncodeME, encode_as_me_bytes;
+    EncodeNE, encode_as_ne_bytes
+);
+
+// Tuple elements are packed one after another in order.
+macro_rules! impl_codec_for_tuple {
+    ($($type:ident $var:ident),+) => {
+        impl<$($type: PackedSize),+> PackedSize for ($($type,)+) {
+            const PACKED_LEN: usize = 0 $(+ $type::PACKED_LEN)+;
+            const ENDIAN_SENSITIVE: bool = false $(|| $type::ENDIAN_SENSITIVE)+;
+        }
+
+        impl_codec_for_tuple!(@encode EncodeLE, encode_as_le_bytes, $($type $var),+);
+        impl_codec_for_tuple!(@encode EncodeBE, encode_as_be_bytes, $($type $var),+);
+        impl_codec_for_tuple!(@encode EncodeME, encode_as_me_bytes, $($type $var),+);
+        impl_codec_for_tuple!(@decode DecodeLE, decode_from_le_bytes, try_decode_from_le_bytes, $($type),+);
+        impl_codec_for_tuple!(@decode DecodeBE, decode_from_be_bytes, try_decode_from_be_bytes, $($type),+);
+        impl_codec_for_tuple!(@encode EncodeNE, encode_as_ne_bytes, $($type $var),+);
+        impl_codec_for_tuple!(@decode DecodeME, decode_from_me_bytes, try_decode_from_me_bytes, $($type),+);
+        impl_codec_for_tuple!(@decode DecodeNE, decode_from_ne_bytes, try_decode_from_ne_bytes, $($type),+);
+
+        impl<$($type: HashLE),+> HashLE for ($($type,)+) {
+            #[inline]
+            fn hash_le<H: Hasher>(&self, state: &mut H) {
+                let ($($var,)+) = self;
+                $($var.hash_le(state);)+
+            }
+        }
+
+        impl<$($type: SwapEndian),+> SwapEndian for ($($type,)+) {
+            #[inline]
+            fn swap_endian_in_buffer(bytes: &mut [u8]) {
+                let mut offset = 0;
+                $(
+                    $type::swap_endian_in_buffer(&mut bytes[offset..offset + $type::PACKED_LEN]);
+                    offset += $type::PACKED_LEN;
+                )+
+                debug_assert_eq!(offset, bytes.len());
+            }
+        }
+    };
+    (@encode $trait:ident, $method:ident, $($type:ident $var:ident),+) => {
+        impl<$($type: $trait),+> $trait for ($($type,)+) {
+            #[inline]
+            fn $method(&self, bytes: &mut [u8]) -> usize {
+                let ($($var,)+) = self;
+                let mut offset = 0;
+                $(
+                    offset += $var.$method(&mut bytes[offset..offset + $type::PACKED_LEN]);
+                )+
+                debug_assert_eq!(offset, bytes.len());
+                offset
+            }
+        }
+    };
+    (@decode $trait:ident, $method:ident, $try_method:ident, $($type:ident),+) => {
+        impl<$($type: $trait),+> $trait for ($($type,)+) {
+            #[inline]
+            #[allow(unused_assignments)]
+            fn $method(bytes: &[u8]) -> Self {
+                let mut offset = 0;
+                ($({
+                    let value = $type::$method(&bytes[offset..offset + $type::PACKED_LEN]);
+                    offset += $type::PACKED_LEN;
+                    value
+                },)+)
+            }
+
+            #[inline]
+            #[allow(unused_assignments)]
+            fn $try_method(bytes: &[u8]) -> Result<Self, EndianError> {
+                check_decode_len(bytes.len(), Self::PACKED_LEN)?;
+                let mut offset = 0;
+                Ok(($({
+                    let value = $type::$try_method(&bytes[offset..offset + $type::PACKED_LEN])?;
+                    offset += $type::PACKED_LEN;
+                    value
+                },)+))
+            }
+        }
+    };
+}
+
+impl_codec_for_tuple!(T0 t0);
+impl_codec_for_tuple!(T0 t0, T1 t1);
+impl_codec_for_tuple!(T0 t0, T1 t1, T2 t2);
+impl_codec_for_tuple!(T0 t0, T1 t1, T2 t2, T3 t3);
+impl_codec_for_tuple!(T0 t0, T1 t1, T2 t2, T3 t3, T4 t4);
+impl_codec_for_tuple!(T0 t0, T1 t1, T2 t2, T3 t3, T4 t4, T5 t5);
+impl_codec_for_tuple!(T0 t0, T1 t1, T2 t2, T3 t3, T4 t4, T5 t5, T6 t6);
+impl_codec_for_tuple!(T0 t0, T1 t1, T2 t2, T3 t3, T4 t4, T5 t5, T6 t6, T7 t7);
+impl_codec_for_tuple!(T0 t0, T1 t1, T2 t2, T3 t3, T4 t4, T5 t5, T6 t6, T7 t7, T8 t8);
+impl_codec_for_tuple!(T0 t0, T1 t1, T2 t2, T3 t3, T4 t4, T5 t5, T6 t6, T7 t7, T8 t8, T9 t9);
+impl_codec_for_tuple!(T0 t0, T1 t1, T2 t2, T3 t3, T4 t4, T5 t5, T6 t6, T7 t7, T8 t8, T9 t9, T10 t10);
+impl_codec_for_tuple!(T0 t0, T1 t1, T2 t2, T3 t3, T4 t4, T5 t5, T6 t6, T7 t7, T8 t8, T9 t9, T10 t10, T11 t11);
+
+/// Encode a value, decode it back and assert both are equal.
+///
+/// Takes the type, the value and the endianness (`le`, `be` or `me`) and evaluates to the encoded
+/// bytes. Optionally the expected bytes can be given as the last argument; they are compared with
+/// the encoded ones and their length with `PACKED_LEN`. The type has to implement `PartialEq` and
+/// `Debug`.
+///
+/// ```rust
+/// use endian_codec::roundtrip;
+///
+/// roundtrip!(u16, 0x0102, le, [0x02, 0x01]);
+/// let bytes = roundtrip!(u32, 7, be);
+/// assert_eq!([0, 0, 0, 7], bytes);
+/// ```
+#[macro_export]
+macro_rules! roundtrip {
+    ($type:ty, $value:expr, le) => {
+        $crate::roundtrip!(@run $type, $value, EncodeLE, encode_as_le_bytes, DecodeLE, decode_from_le_bytes)
+    };
+    ($type:ty, $value:expr, be) => {
+        $crate::roundtrip!(@run $type, $value, EncodeBE, encode_as_be_bytes, DecodeBE, decode_from_be_bytes)
+    };
+    ($type:ty, $value:expr, me) => {
+        $crate::roundtrip!(@run $type, $value, EncodeME, encode_as_me_bytes, DecodeME, decode_from_me_bytes)
+    };
+    ($type:ty, $value:expr, $endian:ident, $expected:expr) => {{
+        let bytes = $crate::roundtrip!($type, $value, $endian);
+        let expected = $expected;
+        assert_eq!(
+            <$type as $crate::PackedSize>::PACKED_LEN,
+            expected.len(),
+            "PACKED_LEN differs from the expected bytes"
+        );
+        assert_eq!(&expected[..], &bytes[..]);
+        bytes
+    }};
+    (@run $type:ty, $value:expr, $encode:ident, $encode_fn:ident, $decode:ident, $decode_fn:ident) => {{
+        let value: $type = $value;
+        let mut bytes = [0u8; <$type as $crate::PackedSize>::PACKED_LEN];
+        <$type as $crate::$encode>::$encode_fn(&value, &mut bytes);
+        assert_eq!(value, <$type as $crate::$decode>::$decode_fn(&bytes));
+        bytes
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate std;
+    use std::{vec, vec::Vec};
+
+    #[test]
+    fn derive_endian_size() {
+        #[derive(PackedSize)]
+        struct A {}
+        assert_eq!(A::PACKED_LEN, 0);
+
+        #[derive(PackedSize)]
+        struct B {
+            _a: u16,
+        }
+        assert_eq!(B::PACKED_LEN, 2);
+
+        #[derive(PackedSize)]
+        struct C {
+            _a: u16,
+            _b: u16,
+        }
+        assert_eq!(C::PACKED_LEN, 2 + 2);
+    }
+
+    #[test]
+    fn derive_littlendian_serialize() {
+        #[derive(PackedSize, EncodeLE)]
+        struct Example {
+            a: u16,
+        }
+
+        let t = Example { a: 5 };
+        let mut b = [0; 2];
+        t.encode_as_le_bytes(&mut b);
+    }
+
+    #[test]
+    fn derive_bigendian_serialize() {
+        #[derive(PackedSize, EncodeBE)]
+        struct Example {
+            a: u16,
+        }
+
+        let t = Example { a: 5 };
+        let mut b = [0; 2];
+        t.encode_as_be_bytes(&mut b);
+    }
+
+    #[test]
+    fn derive_mixed_endian_serialize() {
+        #[derive(PackedSize, EncodeME, Default)]
+        struct Example {
+            #[endian = "le"]
+            a: u16,
+            #[endian = "be"]
+            b: u16,
+            #[endian = "little"]
+            aa: i16,
+            #[endian = "big"]
+            bb: i16,
+        }
+
+        let t = Example::default();
+        let mut b = [0; 8];
+        t.encode_as_me_bytes(&mut b);
+    }
+
+    #[test]
+    fn derive_mixed_endian_struct_default() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeME, DecodeME)]
+        #[endian(le)]
+        struct Example {
+            a: u16,
+            #[endian = "be"]
+            b: u16,
+            c: u32,
+        }
+
+        let t = Example {
+            a: 0x0102,
+            b: 0x0304,
+            c: 0x0506_0708,
+        };
+        let mut b = [0; Example::PACKED_LEN];
+        t.encode_as_me_bytes(&mut b);
+        assert_eq!([0x02, 0x01, 0x03, 0x04, 0x08, 0x07, 0x06, 0x05], b);
+        assert_eq!(t, Example::decode_from_me_bytes(&b));
+    }
+
+    #[test]
+    fn derive_all_serialize() {
+        #[derive(Default, PackedSize, EncodeLE, EncodeBE, EncodeME)]
+        struct Example {
+            #[endian = "be"]
+            a: u16,
+            b: [u8; 32],
+        }
+
+        let t = Example::default();
+        let mut b = [0; 34];
+        t.encode_as_me_bytes(&mut b);
+        t.encode_as_be_bytes(&mut b);
+        t.encode_as_le_bytes(&mut b);
+    }
+
+    #[test]
+    fn derive_all() {
+        #[derive(
+            Default, PackedSize, EncodeLE, EncodeBE, EncodeME, DecodeLE, DecodeBE, DecodeME,
+        )]
+        struct Example {
+            #[endian = "be"]
+            a: u16,
+        }
+
+        let t = Example::default();
+        let mut b = [0; 2];
+        t.encode_as_me_bytes(&mut b);
+        t.encode_as_be_bytes(&mut b);
+        t.encode_as_le_bytes(&mut b);
+    }
+
+    #[test]
+    fn test_codec_2bytes_primitives() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE)]
+        struct A {
+            a: u16,
+            b: i16,
+        }
+
+        let test = A { a: 0x2F, b: 0x2F00 };
+        assert_eq!(A::PACKED_LEN, 4);
+        let mut bytes = [0; A::PACKED_LEN];
+
+        // LE
+        test.encode_as_le_bytes(&mut bytes);
+        assert_eq!([47, 0, 0, 47], bytes);
+
+        let test_back = A::decode_from_le_bytes(&bytes);
+        assert_eq!(test, test_back);
+
+        //BE
+        test.encode_as_be_bytes(&mut bytes);
+        assert_eq!([0, 47, 47, 0], bytes);
+
+        let test_back = A::decode_from_be_bytes(&bytes);
+        assert_eq!(test, test_back);
+    }
+
+    #[test]
+    fn test_codec_4bytes_primitives() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE)]
+        struct A {
+            a: u32,
+            b: i32,
+        }
+
+        let test = A {
+            a: 0x2F,
+            b: 0x2F000000,
+        };
+        assert_eq!(A::PACKED_LEN, 8);
+        let mut bytes = [0; A::PACKED_LEN];
+
+        // LE
+        test.encode_as_le_bytes(&mut bytes);
+        assert_eq!([47, 0, 0, 0, 0, 0, 0, 47], bytes);
+
+        let test_back = A::decode_from_le_bytes(&bytes);
+        assert_eq!(test, test_back);
+
+        //BE
+        test.encode_as_be_bytes(&mut bytes);
+        assert_eq!([0, 0, 0, 47, 47, 0, 0, 0], bytes);
+
+        let test_back = A::decode_from_be_bytes(&bytes);
+        assert_eq!(test, test_back);
+    }
+
+    #[test]
+    fn test_codec_8bytes_primitives() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE)]
+        struct A {
+            a: u64,
+            b: i64,
+        }
+
+        let test = A {
+            a: 0x2F,
+            b: 0x2F000000_00000000,
+        };
+        assert_eq!(A::PACKED_LEN, 16);
+        let mut bytes = [0; A::PACKED_LEN];
+
+        // LE
+        test.encode_as_le_bytes(&mut bytes);
+        assert_eq!([47, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 47], bytes);
+
+        let test_back = A::decode_from_le_bytes(&bytes);
+        assert_eq!(test, test_back);
+
+        //BE
+        test.encode_as_be_bytes(&mut bytes);
+        assert_eq!([0, 0, 0, 0, 0, 0, 0, 47, 47, 0, 0, 0, 0, 0, 0, 0,], bytes);
+
+        let test_back = A::decode_from_be_bytes(&bytes);
+        assert_eq!(test, test_back);
+    }
+
+    #[test]
+    fn test_codec_16bytes_primitives() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE)]
+        struct A {
+            a: u128,
+            b: i128,
+        }
+
+        let test = A {
+            a: 0x2F,
+            b: 0x2F000000_00000000_00000000_00000000,
+        };
+        assert_eq!(A::PACKED_LEN, 32);
+        let mut bytes = [0; A::PACKED_LEN];
+
+        // LE
+        test.encode_as_le_bytes(&mut bytes);
+        assert_eq!(
+            [
+                47, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 47
+            ],
+            bytes
+        );
+
+        let test_back = A::decode_from_le_bytes(&bytes);
+        assert_eq!(test, test_back);
+
+        //BE
+        test.encode_as_be_bytes(&mut bytes);
+        assert_eq!(
+            [
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 47, 47, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0,
+            ],
+            bytes
+        );
+
+        let test_back = A::decode_from_be_bytes(&bytes);
+        assert_eq!(test, test_back);
+    }
+
+    #[test]
+    fn decode_le_versioned() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, DecodeLEVersioned)]
+        struct A {
+            a: u16,
+            #[endian_codec(since = 2)]
+            b: u32,
+            c: u8,
+        }
+
+        let test = A { a: 1, b: 2, c: 3 };
+        let mut bytes = [0; A::PACKED_LEN];
+        test.encode_as_le_bytes(&mut bytes);
+
+        // v2
+        let (test_back, meta) = A::decode_le_versioned(&bytes, 2);
+        assert_eq!(test, test_back);
+        assert!(meta.defaulted.is_empty());
+
+        // v1
+        let (test_back, meta) = A::decode_le_versioned(&[1, 0, 3], 1);
+        assert_eq!(A { a: 1, b: 0, c: 3 }, test_back);
+        assert_eq!(["b"], meta.defaulted);
+    }
+
+    #[test]
+    fn since_field_defaulted_in_older_version() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, DecodeLEVersioned)]
+        struct A {
+            a: u8,
+            #[endian_codec(since = 2)]
+            b: u16,
+            #[endian_codec(since = 3)]
+            c: u16,
+        }
+
+        assert_eq!(A::PACKED_LEN, 5);
+
+        // encode always writes the current version
+        let test = A { a: 1, b: 2, c: 3 };
+        let mut bytes = [0; A::PACKED_LEN];
+        test.encode_as_le_bytes(&mut bytes);
+        assert_eq!([1, 2, 0, 3, 0], bytes);
+
+        let (test_back, meta) = A::decode_le_versioned(&bytes, 3);
+        assert_eq!(test, test_back);
+        assert!(meta.defaulted.is_empty());
+
+        let (test_back, meta) = A::decode_le_versioned(&[1, 2, 0], 2);
+        assert_eq!(A { a: 1, b: 2, c: 0 }, test_back);
+        assert_eq!(["c"], meta.defaulted);
+
+        let (test_back, meta) = A::decode_le_versioned(&[1], 1);
+        assert_eq!(A { a: 1, b: 0, c: 0 }, test_back);
+        assert_eq!(["b", "c"], meta.defaulted);
+    }
+
+    #[test]
+    fn hex_field() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE)]
+        struct A {
+            a: u8,
+            #[endian_codec(hex)]
+            id: [u8; 4],
+        }
+
+        let test = A {
+            a: 1,
+            id: [0xDE, 0xAD, 0x0B, 0xEF],
+        };
+        assert_eq!(A::PACKED_LEN, 1 + 8);
+        let mut bytes = [0; A::PACKED_LEN];
+
+        test.encode_as_le_bytes(&mut bytes);
+        assert_eq!(b"\x01dead0bef", &bytes);
+
+        let test_back = A::decode_from_le_bytes(&bytes);
+        assert_eq!(test, test_back);
+
+        // uppercase digits are accepted too
+        let test_back = A::decode_from_le_bytes(b"\x01DEAD0BEF");
+        assert_eq!(test, test_back);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid hex digit")]
+    fn hex_field_rejects_non_hex() {
+        #[derive(Debug, PackedSize, DecodeLE)]
+        struct A {
+            #[endian_codec(hex)]
+            _id: [u8; 4],
+        }
+
+        A::decode_from_le_bytes(b"dead0bex");
+    }
+
+    #[test]
+    fn zero_sized_fields() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeME, DecodeME)]
+        struct A {
+            #[endian = "be"]
+            a: u16,
+            _unit: (),
+            _marker: PhantomData<u64>,
+        }
+
+        let test = A {
+            a: 0x0102,
+            _unit: (),
+            _marker: PhantomData,
+        };
+        assert_eq!(A::PACKED_LEN, 2);
+        let mut bytes = [0; A::PACKED_LEN];
+
+        test.encode_as_me_bytes(&mut bytes);
+        assert_eq!([1, 2], bytes);
+
+        let test_back = A::decode_from_me_bytes(&bytes);
+        assert_eq!(test, test_back);
+    }
+
+    #[test]
+    fn encode_into_byte_order() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, EncodeBE, DecodeLE, DecodeBE)]
+        struct A {
+            a: u16,
+            b: u32,
+        }
+
+        let test = A {
+            a: 0x0102,
+            b: 0x0304_0506,
+        };
+        let mut bytes = [0; A::PACKED_LEN];
+        let mut expected = [0; A::PACKED_LEN];
+
+        // LE
+        assert_eq!(A::PACKED_LEN, test.encode_into::<LittleEndian>(&mut bytes));
+        test.encode_as_le_bytes(&mut expected);
+        assert_eq!(expected, bytes);
+        assert_eq!(test, A::decode_from::<LittleEndian>(&bytes));
+
+        // BE
+        assert_eq!(A::PACKED_LEN, test.encode_into::<BigEndian>(&mut bytes));
+        test.encode_as_be_bytes(&mut expected);
+        assert_eq!(expected, bytes);
+        assert_eq!(test, A::decode_from::<BigEndian>(&bytes));
+    }
+
+    #[test]
+    fn assert_field_order() {
+        #[derive(PackedSize)]
+        #[endian_codec(assert_field_order("_a", "_b"))]
+        struct A {
+            _a: u8,
+            _b: u16,
+        }
+        assert_eq!(A::PACKED_LEN, 3);
+    }
+
+    #[test]
+    fn assert_packed_len() {
+        #[derive(PackedSize, EncodeLE)]
+        #[endian_codec(assert_packed_len = 6)]
+        struct Version {
+            major: u16,
+            minor: u16,
+            patch: u16,
+        }
+
+        let mut bytes = [0; Version::PACKED_LEN];
+        Version {
+            major: 1,
+            minor: 2,
+            patch: 3,
+        }
+        .encode_as_le_bytes(&mut bytes);
+        assert_eq!([1, 0, 2, 0, 3, 0], bytes);
+    }
+
+    #[test]
+    fn test_codec_ordering() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE)]
+        struct A {
+            a: Ordering,
+            b: Ordering,
+            c: Ordering,
+        }
+
+        let test = A {
+            a: Ordering::Less,
+            b: Ordering::Equal,
+            c: Ordering::Greater,
+        };
+        assert_eq!(A::PACKED_LEN, 3);
+        let mut bytes = [0; A::PACKED_LEN];
+
+        // LE
+        test.encode_as_le_bytes(&mut bytes);
+        assert_eq!([0xFF, 0, 1], bytes);
+
+        let test_back = A::decode_from_le_bytes(&bytes);
+        assert_eq!(test, test_back);
+
+        //BE
+        test.encode_as_be_bytes(&mut bytes);
+        assert_eq!([0xFF, 0, 1], bytes);
+
+        let test_back = A::decode_from_be_bytes(&bytes);
+        assert_eq!(test, test_back);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid Ordering value: 2")]
+    fn test_codec_ordering_invalid() {
+        Ordering::decode_from_le_bytes(&[2]);
+    }
+
+    #[test]
+    fn test_codec_ordering_try_invalid() {
+        assert_eq!(
+            Ok(Ordering::Less),
+            Ordering::try_decode_from_be_bytes(&[0xff])
+        );
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            Ordering::try_decode_from_le_bytes(&[2])
+        );
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            Ordering::try_decode_from_ne_bytes(&[0x80])
+        );
+    }
+
+    #[test]
+    fn decode_advance() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE, DecodeBE)]
+        struct A {
+            a: u16,
+        }
+
+        let bytes = [1, 0, 2, 0, 0, 0, 0, 3];
+        let mut input = &bytes[..];
+        let a: A = decode_le_advance(&mut input).unwrap();
+        let b: u32 = decode_le_advance(&mut input).unwrap();
+        let c: A = decode_be_advance(&mut input).unwrap();
+        assert_eq!(A { a: 1 }, a);
+        assert_eq!(2, b);
+        assert_eq!(A { a: 3 }, c);
+        assert!(input.is_empty());
+
+        let mut input = &bytes[..3];
+        assert_eq!(Ok(A { a: 1 }), decode_le_advance(&mut input));
+        assert_eq!(
+            Err(EndianError::BufferTooSmall { needed: 2, got: 1 }),
+            decode_le_advance::<A>(&mut input)
+        );
+        assert_eq!([2], input);
+    }
+
+    #[test]
+    fn q15_field() {
+        #[derive(Debug, PartialEq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE)]
+        struct A {
+            #[endian_codec(q = 15)]
+            a: f32,
+            #[endian_codec(q = 15)]
+            b: f32,
+        }
+
+        let test = A {
+            a: 0.999_969_5, // largest Q15 value: 32767 / 32768
+            b: -1.0,
+        };
+        assert_eq!(A::PACKED_LEN, 4);
+        let mut bytes = [0; A::PACKED_LEN];
+
+        // LE
+        test.encode_as_le_bytes(&mut bytes);
+        assert_eq!([0xFF, 0x7F, 0x00, 0x80], bytes);
+
+        let test_back = A::decode_from_le_bytes(&bytes);
+        assert_eq!(test, test_back);
+
+        //BE
+        test.encode_as_be_bytes(&mut bytes);
+        assert_eq!([0x7F, 0xFF, 0x80, 0x00], bytes);
+
+        let test_back = A::decode_from_be_bytes(&bytes);
+        assert_eq!(test, test_back);
+
+        // out of range values saturate
+        A { a: 1.0, b: -1.5 }.encode_as_le_bytes(&mut bytes);
+        assert_eq!([0xFF, 0x7F, 0x00, 0x80], bytes);
+
+        // values round to the nearest representable one
+        A {
+            a: 0.000_020,  // 0.655 / 32768
+            b: -0.000_020, // -0.655 / 32768
+        }
+        .encode_as_le_bytes(&mut bytes);
+        assert_eq!([0x01, 0x00, 0xFF, 0xFF], bytes);
+    }
+
+    #[test]
+    fn hash_le() {
+        // FNV-1a, so the test doesn't need std
+        struct Fnv(u64);
+
+        impl Hasher for Fnv {
+            fn finish(&self) -> u64 {
+                self.0
+            }
+
+            fn write(&mut self, bytes: &[u8]) {
+                for byte in bytes {
+                    self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x100_0000_01b3);
+                }
+            }
+        }
+
+        fn hash<T: HashLE>(value: &T) -> u64 {
+            let mut hasher = Fnv(0xcbf2_9ce4_8422_2325);
+            value.hash_le(&mut hasher);
+            hasher.finish()
+        }
+
+        #[derive(PackedSize, EncodeLE, HashLE)]
+        struct A {
+            a: u16,
+            b: [u8; 2],
+            #[endian_codec(hex)]
+            c: u8,
+        }
+
+        let test = A {
+            a: 1,
+            b: [2, 3],
+            c: 4,
+        };
+        assert_eq!(hash(&test), hash(&A { ..test }));
+        assert_ne!(hash(&test), hash(&A { a: 2, ..test }));
+
+        // same bytes as encoded
+        let mut bytes = [0; A::PACKED_LEN];
+        test.encode_as_le_bytes(&mut bytes);
+        let mut hasher = Fnv(0xcbf2_9ce4_8422_2325);
+        hasher.write(&bytes);
+        assert_eq!(hasher.finish(), hash(&test));
+
+        // enums hash their encoded index or discriminant
+        #[derive(Clone, Copy, PackedSize, EncodeLE, HashLE)]
+        enum Kind {
+            Ping,
+            Data,
+        }
+        #[derive(Clone, Copy, PackedSize, EncodeLE, HashLE)]
+        #[repr(u16)]
+        enum Code {
+            Ok = 200,
+            NotFound = 404,
+        }
+        let hash_bytes = |bytes: &[u8]| {
+            let mut hasher = Fnv(0xcbf2_9ce4_8422_2325);
+            hasher.write(bytes);
+            hasher.finish()
+        };
+        assert_ne!(hash(&Kind::Ping), hash(&Kind::Data));
+        assert_eq!(hash_bytes(&[1]), hash(&Kind::Data));
+        assert_ne!(hash(&Code::Ok), hash(&Code::NotFound));
+        assert_eq!(hash_bytes(&404u16.to_le_bytes()), hash(&Code::NotFound));
+    }
+
+    #[test]
+    fn decode_with_endian() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeBE, DecodeWithEndian)]
+        struct A {
+            a: u16,
+            b: u32,
+        }
+
+        let test = A { a: 1, b: 2 };
+        let mut bytes = [0; A::PACKED_LEN];
+        test.encode_as_be_bytes(&mut bytes);
+
+        let test_back = A::decode_with_endian(&bytes, |_| Endian::Big);
+        assert_eq!(test, test_back);
+
+        // toggle only `b`
+        let mut bytes = [0; A::PACKED_LEN];
+        bytes[..2].copy_from_slice(&1u16.to_be_bytes());
+        bytes[2..].copy_from_slice(&2u32.to_le_bytes());
+        let test_back =
+            A::decode_with_endian(
+                &bytes,
+                |i| {
+                    if i == 1 {
+                        Endian::Little
+                    } else {
+                        Endian::Big
+                    }
+                },
+            );
+        assert_eq!(test, test_back);
+    }
+
+    #[test]
+    fn test_codec_socket_addr_v4() {
+        let test = SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080);
+        assert_eq!(SocketAddrV4::PACKED_LEN, 6);
+        let mut bytes = [0; SocketAddrV4::PACKED_LEN];
+
+        // LE
+        test.encode_as_le_bytes(&mut bytes);
+        assert_eq!([127, 0, 0, 1, 0x90, 0x1F], bytes);
+
+        let test_back = SocketAddrV4::decode_from_le_bytes(&bytes);
+        assert_eq!(test, test_back);
+
+        //BE
+        test.encode_as_be_bytes(&mut bytes);
+        assert_eq!([127, 0, 0, 1, 0x1F, 0x90], bytes);
+
+        let test_back = SocketAddrV4::decode_from_be_bytes(&bytes);
+        assert_eq!(test, test_back);
+    }
+
+    #[test]
+    fn derive_endian_newtypes() {
+        #[derive(
+            Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE, EndianNewtypes,
+        )]
+        struct Frame {
+            a: u16,
+        }
+
+        assert_eq!(LeFrame::PACKED_LEN, Frame::PACKED_LEN);
+        assert_eq!(BeFrame::PACKED_LEN, Frame::PACKED_LEN);
+        let mut bytes = [0; Frame::PACKED_LEN];
+
+        // LE
+        LeFrame(Frame { a: 1 }).encode_as_le_bytes(&mut bytes);
+        assert_eq!([1, 0], bytes);
+        assert_eq!(Frame { a: 1 }, LeFrame::decode_from_le_bytes(&bytes).0);
+
+        //BE
+        BeFrame(Frame { a: 1 }).encode_as_be_bytes(&mut bytes);
+        assert_eq!([0, 1], bytes);
+        assert_eq!(Frame { a: 1 }, BeFrame::decode_from_be_bytes(&bytes).0);
+
+        // generic structs
+        #[derive(
+            Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE, EndianNewtypes,
+        )]
+        struct Pair<T> {
+            a: T,
+            b: T,
+        }
+
+        let mut bytes = [0; Pair::<u16>::PACKED_LEN];
+        LePair(Pair { a: 1u16, b: 2 }).encode_as_le_bytes(&mut bytes);
+        assert_eq!([1, 0, 2, 0], bytes);
+        assert_eq!(
+            Pair { a: 1u16, b: 2 },
+            LePair::decode_from_le_bytes(&bytes).0
+        );
+        BePair(Pair { a: 1u16, b: 2 }).encode_as_be_bytes(&mut bytes);
+        assert_eq!([0, 1, 0, 2], bytes);
+        assert_eq!(
+            Pair { a: 1u16, b: 2 },
+            BePair::decode_from_be_bytes(&bytes).0
+        );
+    }
+
+    #[test]
+    fn decode_iter() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE, DecodeBE)]
+        struct A {
+            a: u16,
+        }
+
+        let bytes = [1, 0, 2, 0, 3, 0, 4];
+        let mut records = Vec::new();
+        records.extend(decode_iter_le::<A>(&bytes));
+        assert_eq!(vec![A { a: 1 }, A { a: 2 }, A { a: 3 }], records);
+
+        let mut iter = decode_iter_be::<A>(&bytes);
+        assert_eq!(3, iter.len());
+        assert_eq!(Some(A { a: 256 }), iter.next());
+        assert_eq!(2, iter.by_ref().count());
+        assert_eq!(None, iter.next());
+        assert_eq!([4], iter.remainder());
+    }
+
+    #[test]
+    fn enum_variant_index() {
+        #[derive(
+            Debug,
+            PartialEq,
+            Eq,
+            PackedSize,
+            EncodeLE,
+            DecodeLE,
+            EncodeBE,
+            DecodeBE,
+            EncodeME,
+            DecodeME,
+        )]
+        enum Kind {
+            Ping,
+            Data,
+            Close,
+        }
+
+        assert_eq!(Kind::PACKED_LEN, 1);
+        let mut bytes = [0; Kind::PACKED_LEN];
+
+        for (kind, index) in [(Kind::Ping, 0), (Kind::Data, 1), (Kind::Close, 2)] {
+            kind.encode_as_le_bytes(&mut bytes);
+            assert_eq!([index], bytes);
+            assert_eq!(kind, Kind::decode_from_le_bytes(&bytes));
+
+            kind.encode_as_be_bytes(&mut bytes);
+            assert_eq!([index], bytes);
+            assert_eq!(kind, Kind::decode_from_be_bytes(&bytes));
+
+            kind.encode_as_me_bytes(&mut bytes);
+            assert_eq!([index], bytes);
+            assert_eq!(kind, Kind::decode_from_me_bytes(&bytes));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid Kind variant index: 3")]
+    fn enum_invalid_variant_index() {
+        #[derive(Debug, PackedSize, DecodeLE)]
+        enum Kind {
+            _Ping,
+            _Data,
+            _Close,
+        }
+
+        Kind::decode_from_le_bytes(&[3]);
+    }
+
+    #[test]
+    fn enum_variant_index_full_u8() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE)]
+        #[rustfmt::skip]
+        enum Opcode {
+            V0, V1, V2, V3, V4, V5, V6, V7, V8, V9, V10, V11, V12, V13, V14, V15,
+            V16, V17, V18, V19, V20, V21, V22, V23, V24, V25, V26, V27, V28, V29, V30, V31,
+            V32, V33, V34, V35, V36, V37, V38, V39, V40, V41, V42, V43, V44, V45, V46, V47,
+            V48, V49, V50, V51, V52, V53, V54, V55, V56, V57, V58, V59, V60, V61, V62, V63,
+            V64, V65, V66, V67, V68, V69, V70, V71, V72, V73, V74, V75, V76, V77, V78, V79,
+            V80, V81, V82, V83, V84, V85, V86, V87, V88, V89, V90, V91, V92, V93, V94, V95,
+            V96, V97, V98, V99, V100, V101, V102, V103, V104, V105, V106, V107, V108, V109, V110, V111,
+            V112, V113, V114, V115, V116, V117, V118, V119, V120, V121, V122, V123, V124, V125, V126, V127,
+            V128, V129, V130, V131, V132, V133, V134, V135, V136, V137, V138, V139, V140, V141, V142, V143,
+            V144, V145, V146, V147, V148, V149, V150, V151, V152, V153, V154, V155, V156, V157, V158, V159,
+            V160, V161, V162, V163, V164, V165, V166, V167, V168, V169, V170, V171, V172, V173, V174, V175,
+            V176, V177, V178, V179, V180, V181, V182, V183, V184, V185, V186, V187, V188, V189, V190, V191,
+            V192, V193, V194, V195, V196, V197, V198, V199, V200, V201, V202, V203, V204, V205, V206, V207,
+            V208, V209, V210, V211, V212, V213, V214, V215, V216, V217, V218, V219, V220, V221, V222, V223,
+            V224, V225, V226, V227, V228, V229, V230, V231, V232, V233, V234, V235, V236, V237, V238, V239,
+            V240, V241, V242, V243, V244, V245, V246, V247, V248, V249, V250, V251, V252, V253, V254, V255,
+        }
+
+        roundtrip!(Opcode, Opcode::V0, le, [0]);
+        roundtrip!(Opcode, Opcode::V1, le, [1]);
+        roundtrip!(Opcode, Opcode::V255, le, [255]);
+    }
+
+    #[test]
+    fn enum_with_discriminants() {
+        #[derive(
+            Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE, SwapEndian,
+        )]
+        #[repr(u16)]
+        enum MessageType {
+            Hello = 0x0100,
+            Data = 0x0200,
+            Bye = 0xFFFF,
+        }
+
+        assert_eq!(2, MessageType::PACKED_LEN);
+        let mut bytes = [0; 2];
+        MessageType::Data.encode_as_be_bytes(&mut bytes);
+        assert_eq!([2, 0], bytes);
+        assert_eq!(MessageType::Data, MessageType::decode_from_be_bytes(&bytes));
+        MessageType::swap_endian_in_buffer(&mut bytes);
+        assert_eq!(
+            Ok(MessageType::Data),
+            MessageType::try_decode_from_le_bytes(&bytes)
+        );
+
+        MessageType::Bye.encode_as_le_bytes(&mut bytes);
+        assert_eq!([0xFF, 0xFF], bytes);
+        assert_eq!(
+            Ok(MessageType::Hello),
+            MessageType::try_decode_from_le_bytes(&[0, 1])
+        );
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            MessageType::try_decode_from_be_bytes(&[0, 1])
+        );
+
+        // variants without a repr are stored by index
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE)]
+        enum Kind {
+            _Ping,
+            _Data,
+        }
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            Kind::try_decode_from_le_bytes(&[2])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid Signal discriminant: 3")]
+    fn enum_invalid_discriminant() {
+        #[derive(Debug, PackedSize, DecodeBE)]
+        #[repr(u32)]
+        enum Signal {
+            _Start = 1,
+            _Stop = 2,
+        }
+
+        Signal::decode_from_be_bytes(&[0, 0, 0, 3]);
+    }
+
+    #[test]
+    fn decode_le_nonzero() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE)]
+        struct Entry {
+            id: u16,
+            offset: u32,
+        }
+
+        assert_eq!(None, Entry::decode_le_nonzero(&[0; Entry::PACKED_LEN]));
+        assert_eq!(
+            Some(Entry { id: 0, offset: 1 }),
+            Entry::decode_le_nonzero(&[0, 0, 1, 0, 0, 0])
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn decode_from_reader_tracks_position() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE)]
+        struct Record {
+            id: u16,
+            value: u32,
+        }
+
+        let mut reader = std::io::Cursor::new([1, 0, 10, 0, 0, 0, 2, 0, 20, 0, 0, 0, 3]);
+        let mut pos = 0;
+
+        let (first, len) = decode_le_from_reader::<Record, _>(&mut reader).unwrap();
+        pos += len;
+        assert_eq!(Record { id: 1, value: 10 }, first);
+        assert_eq!(6, pos);
+
+        let (second, len) = decode_le_from_reader::<Record, _>(&mut reader).unwrap();
+        pos += len;
+        assert_eq!(Record { id: 2, value: 20 }, second);
+        assert_eq!(12, pos);
+        assert_eq!(pos as u64, reader.position());
+
+        let err = decode_le_from_reader::<Record, _>(&mut reader).unwrap_err();
+        assert_eq!(std::io::ErrorKind::UnexpectedEof, err.kind());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn decode_from_reader_invalid_value() {
+        #[derive(Debug, PackedSize, DecodeLE)]
+        enum Kind {
+            _Ping,
+            _Data,
+        }
+
+        let err = decode_le_from_reader::<Kind, _>(&mut &[2][..]).unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+        assert_eq!(
+            Some(&EndianError::InvalidValue),
+            err.get_ref().and_then(|err| err.downcast_ref())
+        );
+    }
+
+    #[test]
+    fn debug_layout_fields() {
+        #[derive(PackedSize, DebugLayout)]
+        #[endian(le)]
+        struct Example {
+            _a: u16,
+            #[endian = "be"]
+            _b: u32,
+            _c: [u8; 3],
+        }
+
+        assert_eq!(
+            Example::FIELDS,
+            [
+                FieldLayout {
+                    name: "_a",
+                    offset: 0,
+                    size: 2,
+                    endian: Some(Endian::Little),
+                },
+                FieldLayout {
+                    name: "_b",
+                    offset: 2,
+                    size: 4,
+                    endian: Some(Endian::Big),
+                },
+                FieldLayout {
+                    name: "_c",
+                    offset: 6,
+                    size: 3,
+                    endian: Some(Endian::Little),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn debug_layout_schema() {
+        #[derive(PackedSize, DebugLayout)]
+        struct Example {
+            #[endian = "le"]
+            _a: u16,
+            #[endian = "be"]
+            _b: u32,
+            #[endian = "pdp"]
+            _c: i32,
+        }
+
+        assert_eq!(
+            Example::SCHEMA,
+            [("_a", 0, 2, "le"), ("_b", 2, 4, "be"), ("_c", 6, 4, "pdp")]
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn debug_layout_string() {
+        #[derive(PackedSize, DebugLayout)]
+        struct Example {
+            #[endian = "le"]
+            _a: u16,
+            #[endian = "be"]
+            _b: u32,
+            _c: u8,
+        }
+
+        let layout = Example::debug_layout();
+        assert!(layout.contains("_a: offset 0, size 2, le"));
+        assert!(layout.contains("_b: offset 2, size 4, be"));
+        assert!(layout.contains("_c: offset 6, size 1, me"));
+    }
+
+    #[test]
+    fn decode_default_on_short() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, DecodeBE, DecodeME)]
+        #[endian(le)]
+        #[endian_codec(default_on_short)]
+        struct Example {
+            a: u16,
+            b: u32,
+            c: u8,
+        }
+
+        let full = Example {
+            a: 0x0102,
+            b: 0x0304_0506,
+            c: 7,
+        };
+        let mut bytes = [0; Example::PACKED_LEN];
+        full.encode_as_le_bytes(&mut bytes);
+
+        assert_eq!(full, Example::decode_from_le_bytes(&bytes));
+        assert_eq!(
+            Example { c: 0, ..full },
+            Example::decode_from_le_bytes(&bytes[..6])
+        );
+        // `b` is cut in the middle, so it's defaulted too
+        assert_eq!(
+            Example {
+                a: 0x0102,
+                b: 0,
+                c: 0
+            },
+            Example::decode_from_le_bytes(&bytes[..4])
+        );
+        assert_eq!(
+            Example {
+                a: 0x0102,
+                b: 0,
+                c: 0
+            },
+            Example::decode_from_le_bytes(&bytes[..2])
+        );
+        assert_eq!(
+            Example { a: 0, b: 0, c: 0 },
+            Example::decode_from_le_bytes(&bytes[..1])
+        );
+        assert_eq!(
+            Example { a: 0, b: 0, c: 0 },
+            Example::decode_from_be_bytes(&[])
+        );
+        assert_eq!(
+            Example {
+                a: 0x0201,
+                b: 0,
+                c: 0
+            },
+            Example::decode_from_me_bytes(&[1, 2, 3])
+        );
+
+        // fallible decoders default the same fields, but still reject a long buffer
+        assert_eq!(
+            Ok(Example { c: 0, ..full }),
+            Example::try_decode_from_le_bytes(&bytes[..6])
+        );
+        assert_eq!(
+            Err(EndianError::BufferTooLarge { needed: 7, got: 8 }),
+            Example::try_decode_from_le_bytes(&[0; 8])
+        );
+        #[cfg(feature = "alloc")]
+        {
+            assert_eq!(
+                Ok(Example { c: 0, ..full }),
+                Example::decode_le_collect_errors(&bytes[..6])
+            );
+            assert_eq!(
+                EndianError::BufferTooLarge { needed: 7, got: 8 },
+                Example::decode_le_collect_errors(&[0; 8]).unwrap_err()[0].error
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "buffer is longer than PACKED_LEN")]
+    fn decode_default_on_short_long_buffer() {
+        #[derive(Debug, PackedSize, DecodeLE)]
+        #[endian_codec(default_on_short)]
+        struct Example {
+            _a: u16,
+            _b: u16,
+        }
+
+        Example::decode_from_le_bytes(&[1, 0, 2, 0, 3]);
+    }
+
+    #[test]
+    fn encode_tuple_of_refs() {
+        let header = 0x0102u16;
+        let body = 0x0304_0506u32;
+
+        let mut pair = [0; <(&u16, &u32)>::PACKED_LEN];
+        (&header, &body).encode_as_le_bytes(&mut pair);
+
+        let mut manual = [0; 6];
+        header.encode_as_le_bytes(&mut manual[..2]);
+        body.encode_as_le_bytes(&mut manual[2..]);
+        assert_eq!(manual, pair);
+
+        assert_eq!((header, body), <(u16, u32)>::decode_from_le_bytes(&pair));
+    }
+
+    #[test]
+    fn decode_slice_into() {
+        let bytes = [1, 0, 0, 2, 0, 3, 4];
+
+        // more records than `out` can hold
+        let mut out = [0u16; 2];
+        assert_eq!(2, decode_slice_le_into(&bytes, &mut out));
+        assert_eq!([1, 0x0200], out);
+
+        // more room in `out` than records
+        let mut out = [0u16; 5];
+        assert_eq!(3, decode_slice_be_into(&bytes, &mut out));
+        assert_eq!([0x0100, 2, 3, 0, 0], out);
+
+        let mut out = [0u8; 8];
+        assert_eq!(7, decode_slice_me_into(&bytes, &mut out));
+        assert_eq!([1, 0, 0, 2, 0, 3, 4, 0], out);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn endian_error_into_io_error() {
+        use std::io::Read;
+
+        fn read_id(reader: &mut impl Read) -> std::io::Result<u32> {
+            let mut frame = Vec::new();
+            reader.read_to_end(&mut frame)?;
+            Ok(decode_le_advance(&mut frame.as_slice())?)
+        }
+
+        assert_eq!(4, read_id(&mut &[4, 0, 0, 0][..]).unwrap());
+
+        let err = read_id(&mut &[4, 0][..]).unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+        assert_eq!(
+            Some(&EndianError::BufferTooSmall { needed: 4, got: 2 }),
+            err.get_ref().and_then(|err| err.downcast_ref())
+        );
+    }
+
+    #[test]
+    fn enum_custom_tag() {
+        #[derive(
+            Debug,
+            PartialEq,
+            Eq,
+            Clone,
+            Copy,
+            PackedSize,
+            EncodeLE,
+            DecodeLE,
+            EncodeBE,
+            DecodeBE,
+            SwapEndian,
+            HashLE,
+        )]
+        #[endian_codec(tag(encode = "Prime::tag", decode = "Prime::from_tag"))]
+        enum Prime {
+            Two,
+            Three,
+            Five,
+        }
+
+        impl Prime {
+            fn tag(&self) -> u16 {
+                match self {
+                    Prime::Two => 2,
+                    Prime::Three => 3,
+                    Prime::Five => 5,
+                }
+            }
+
+            fn from_tag(tag: u16) -> Option<Self> {
+                match tag {
+                    2 => Some(Prime::Two),
+                    3 => Some(Prime::Three),
+                    5 => Some(Prime::Five),
+                    _ => None,
+                }
+            }
+        }
+
+        assert_eq!(Prime::PACKED_LEN, 2);
+        let mut bytes = [0; Prime::PACKED_LEN];
+        for &(prime, tag) in &[(Prime::Two, 2), (Prime::Three, 3), (Prime::Five, 5)] {
+            prime.encode_as_le_bytes(&mut bytes);
+            assert_eq!([tag, 0], bytes);
+            assert_eq!(prime, Prime::decode_from_le_bytes(&bytes));
+
+            prime.encode_as_be_bytes(&mut bytes);
+            assert_eq!([0, tag], bytes);
+            assert_eq!(prime, Prime::decode_from_be_bytes(&bytes));
+
+            Prime::swap_endian_in_buffer(&mut bytes);
+            assert_eq!([tag, 0], bytes);
+        }
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            Prime::try_decode_from_le_bytes(&[4, 0])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid Prime tag")]
+    fn enum_custom_tag_invalid() {
+        #[derive(Debug, PackedSize, DecodeLE)]
+        #[endian_codec(tag(encode = "Prime::tag", decode = "Prime::from_tag"))]
+        enum Prime {
+            Two,
+        }
+
+        impl Prime {
+            fn tag(&self) -> u8 {
+                2
+            }
+
+            fn from_tag(tag: u8) -> Option<Self> {
+                match tag {
+                    2 => Some(Prime::Two),
+                    _ => None,
+                }
+            }
+        }
+
+        Prime::decode_from_le_bytes(&[4]);
+    }
+
+    #[test]
+    fn roundtrip_macro() {
+        roundtrip!(u16, 0x0102, le, [0x02, 0x01]);
+        roundtrip!(i32, -2, be, [0xff, 0xff, 0xff, 0xfe]);
+        roundtrip!(u8, 7, me, [7]);
+        roundtrip!(Ordering, Ordering::Less, me, [0xff]);
+        roundtrip!((u8, u16), (1, 2), be, [1, 0, 2]);
+        roundtrip!([u8; 3], [1, 2, 3], le);
+    }
+
+    #[test]
+    #[should_panic(expected = "PACKED_LEN differs from the expected bytes")]
+    fn roundtrip_macro_len_mismatch() {
+        roundtrip!(u16, 1, le, [1, 0, 0]);
+    }
+
+    #[test]
+    fn padded_array() {
+        type Users = PaddedArray<u16, 3>;
+        assert_eq!(Users::PACKED_LEN, 2 + 3 * 2);
+        let mut bytes = [0xff; Users::PACKED_LEN];
+
+        let empty = Users::new();
+        empty.encode_as_be_bytes(&mut bytes);
+        assert_eq!([0; Users::PACKED_LEN], bytes);
+        assert_eq!(empty, Users::decode_from_be_bytes(&bytes));
+
+        let mut partial = Users::new();
+        partial.push(0x0102).unwrap();
+        partial.encode_as_be_bytes(&mut bytes);
+        assert_eq!([0, 1, 1, 2, 0, 0, 0, 0], bytes);
+        assert_eq!(partial, Users::decode_from_be_bytes(&bytes));
+
+        let full = Users::from_slice(&[1, 2, 3]).unwrap();
+        full.encode_as_le_bytes(&mut bytes);
+        assert_eq!([3, 0, 1, 0, 2, 0, 3, 0], bytes);
+        let decoded = Users::decode_from_le_bytes(&bytes);
+        assert_eq!([1, 2, 3], decoded.as_slice());
+
+        let mut full = full;
+        assert_eq!(Err(4), full.push(4));
+        assert_eq!(None, Users::from_slice(&[1, 2, 3, 4]));
+    }
+
+    #[test]
+    #[should_panic(expected = "PaddedArray count 4 exceeds capacity 3")]
+    fn padded_array_over_count() {
+        PaddedArray::<u16, 3>::decode_from_le_bytes(&[4, 0, 1, 0, 2, 0, 3, 0]);
+    }
+
+    #[test]
+    fn padded_array_try_decode() {
+        type Users = PaddedArray<u16, 3>;
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            Users::try_decode_from_le_bytes(&[4, 0, 1, 0, 2, 0, 3, 0])
+        );
+        assert_eq!(
+            Err(EndianError::BufferTooSmall { needed: 8, got: 2 }),
+            Users::try_decode_from_be_bytes(&[0, 1])
+        );
+        assert_eq!(
+            Ok(Users::from_slice(&[0x0102]).unwrap()),
+            Users::try_decode_from_be_bytes(&[0, 1, 1, 2, 0, 0, 0, 0])
+        );
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            PaddedArray::<char, 2>::try_decode_from_le_bytes(&[1, 0, 0, 0xD8, 0, 0, 0, 0, 0, 0])
+        );
+    }
+
+    #[test]
+    fn padded_array_mixed_endian() {
+        #[derive(Debug, Default, Clone, Copy, PartialEq, PackedSize, EncodeME, DecodeME)]
+        struct Entry {
+            #[endian = "be"]
+            id: u16,
+            #[endian = "le"]
+            value: u16,
+        }
+
+        let entries = PaddedArray::<Entry, 2>::from_slice(&[Entry { id: 1, value: 2 }]).unwrap();
+        roundtrip!(
+            PaddedArray<Entry, 2>,
+            entries,
+            me,
+            [1, 0, 0, 1, 2, 0, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn hex_encoded_format() {
+        use hex::Encoded;
+        use std::format;
+
+        let value = 0x0a0bu16;
+        assert_eq!("0b0a", format!("{:x}", Encoded::le(&value)));
+        assert_eq!("0A0B", format!("{:X}", Encoded::be(&value)));
+        assert_eq!("00000b0a", format!("{:08x}", Encoded::le(&value)));
+        assert_eq!("    0a0b", format!("{:8x}", Encoded::be(&value)));
+        assert_eq!("0x0a0b", format!("{:#x}", Encoded::be(&value)));
+        assert_eq!("2a", format!("{:02x}", Encoded::me(&42u8)));
+    }
+
+    #[test]
+    fn decode_header_prefix() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE, DecodeBE)]
+        struct Header {
+            kind: u8,
+            len: u16,
+        }
+
+        let frame = [1, 0, 3, 0xAA, 0xBB, 0xCC];
+        let (header, consumed) = decode_prefix_le::<Header>(&frame).unwrap();
+        assert_eq!(
+            Header {
+                kind: 1,
+                len: 0x0300
+            },
+            header
+        );
+        assert_eq!(Header::PACKED_LEN, consumed);
+
+        let (header, consumed) = decode_prefix_be::<Header>(&frame).unwrap();
+        assert_eq!(Header { kind: 1, len: 3 }, header);
+        assert_eq!(
+            [0xAA, 0xBB, 0xCC],
+            frame[consumed..][..usize::from(header.len)]
+        );
+
+        assert_eq!(
+            Err(EndianError::BufferTooSmall { needed: 3, got: 2 }),
+            decode_prefix_le::<Header>(&frame[..2])
+        );
+    }
+
+    #[test]
+    fn default_byte_order() {
+        let mut bytes = [0; 2];
+        assert_eq!(2, 0x0102u16.encode_default(&mut bytes));
+        if cfg!(feature = "default_be") {
+            assert_eq!([1, 2], bytes);
+        } else {
+            assert_eq!([2, 1], bytes);
+        }
+        assert_eq!(0x0102, u16::decode_default(&bytes));
+    }
+
+    #[test]
+    fn enum_field_in_struct() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE)]
+        enum MsgKind {
+            Ping,
+            Data,
+        }
+
+        #[derive(
+            Debug,
+            PartialEq,
+            Eq,
+            PackedSize,
+            EncodeLE,
+            DecodeLE,
+            EncodeBE,
+            DecodeBE,
+            EncodeME,
+            DecodeME,
+        )]
+        struct Packet {
+            #[endian = "be"]
+            kind: MsgKind,
+            #[endian = "le"]
+            body: u32,
+        }
+
+        assert_eq!(Packet::PACKED_LEN, 1 + 4);
+        let packet = Packet {
+            kind: MsgKind::Data,
+            body: 0x0102_0304,
+        };
+        let mut bytes = [0; Packet::PACKED_LEN];
+
+        packet.encode_as_le_bytes(&mut bytes);
+        assert_eq!([1, 4, 3, 2, 1], bytes);
+        assert_eq!(packet, Packet::decode_from_le_bytes(&bytes));
+
+        packet.encode_as_be_bytes(&mut bytes);
+        assert_eq!([1, 1, 2, 3, 4], bytes);
+        assert_eq!(packet, Packet::decode_from_be_bytes(&bytes));
+
+        packet.encode_as_me_bytes(&mut bytes);
+        assert_eq!([1, 4, 3, 2, 1], bytes);
+        assert_eq!(packet, Packet::decode_from_me_bytes(&bytes));
+    }
+
+    #[test]
+    fn decode_le_with_checksum() {
+        use core::hash::BuildHasher;
+        use std::collections::hash_map::RandomState;
+
+        let state = RandomState::new();
+        let bytes = [1, 0, 0, 2];
+        let (value, checksum) = u32::decode_le_with_checksum(&bytes, state.build_hasher());
+        assert_eq!(0x0200_0001, value);
+
+        let mut hasher = state.build_hasher();
+        hasher.write(&bytes);
+        assert_eq!(hasher.finish(), checksum);
+    }
+
+    #[test]
+    fn decode_le_with_checksum_range() {
+        use core::hash::BuildHasher;
+        use std::collections::hash_map::RandomState;
+
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE)]
+        #[endian_codec(checksum_range = "4..")]
+        struct Frame {
+            header: [u8; 4],
+            body: u16,
+        }
+
+        let state = RandomState::new();
+        let bytes = [0xAA, 0xBB, 0xCC, 0xDD, 1, 2];
+        let (value, checksum) = Frame::decode_le_with_checksum(&bytes, state.build_hasher());
+        assert_eq!(
+            Frame {
+                header: [0xAA, 0xBB, 0xCC, 0xDD],
+                body: 0x0201
+            },
+            value
+        );
+
+        let mut hasher = state.build_hasher();
+        hasher.write(&bytes[4..]);
+        assert_eq!(hasher.finish(), checksum);
+
+        // the header doesn't change the checksum
+        let bytes = [0, 0, 0, 0, 1, 2];
+        let (_, other) = Frame::decode_le_with_checksum(&bytes, state.build_hasher());
+        assert_eq!(checksum, other);
+    }
+
+    #[test]
+    fn decode_le_from_array() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE)]
+        struct Version {
+            major: u16,
+            minor: u16,
+            patch: u16,
+        }
+
+        let buf: [u8; Version::PACKED_LEN] = [0, 0, 21, 0, 37, 0];
+        let v = Version::from(&buf);
+        assert_eq!(
+            Version {
+                major: 0,
+                minor: 21,
+                patch: 37
+            },
+            v
+        );
+    }
+
+    #[test]
+    fn computed_count_field() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE)]
+        struct Chat {
+            #[endian_codec(compute = "self.users.iter().filter(|&&u| u != 0).count() as u8")]
+            count: u8,
+            users: [u8; 4],
+        }
+
+        let chat = Chat {
+            count: 0,
+            users: [3, 9, 0, 0],
+        };
+        let mut bytes = [0; Chat::PACKED_LEN];
+        chat.encode_as_le_bytes(&mut bytes);
+        assert_eq!([2, 3, 9, 0, 0], bytes);
+        assert_eq!(
+            Chat {
+                count: 2,
+                users: [3, 9, 0, 0]
+            },
+            Chat::decode_from_le_bytes(&bytes)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "`count` doesn't match its computed value")]
+    fn computed_field_mismatch() {
+        #[derive(Debug, PackedSize, DecodeLE)]
+        struct Frame {
+            #[endian_codec(compute = "self.payload.len() as u16")]
+            count: u16,
+            payload: [u8; 2],
+        }
+
+        Frame::decode_from_le_bytes(&[3, 0, 1, 2]);
+    }
+
+    #[test]
+    fn computed_field_mismatch_error() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE)]
+        struct Frame {
+            #[endian_codec(compute = "self.payload.len() as u16")]
+            count: u16,
+            payload: [u8; 2],
+        }
+
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            Frame::try_decode_from_le_bytes(&[3, 0, 1, 2])
+        );
+        assert_eq!(
+            Ok(Frame {
+                count: 2,
+                payload: [1, 2]
+            }),
+            Frame::try_decode_from_le_bytes(&[2, 0, 1, 2])
+        );
+
+        #[cfg(feature = "alloc")]
+        assert_eq!(
+            Err(alloc::vec![FieldError {
+                field: "count".into(),
+                error: EndianError::InvalidValue,
+            }]),
+            Frame::decode_le_collect_errors(&[3, 0, 1, 2])
+        );
+    }
+
+    #[test]
+    fn swap_endian_in_buffer() {
+        #[derive(
+            Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE, SwapEndian,
+        )]
+        enum Kind {
+            _Ping,
+            Data,
+        }
+
+        #[derive(
+            Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE, SwapEndian,
+        )]
+        struct Inner {
+            a: i16,
+            b: u8,
+        }
+
+        #[derive(
+            Debug, PartialEq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE, SwapEndian,
+        )]
+        struct Example {
+            kind: Kind,
+            id: u32,
+            name: [u8; 3],
+            inner: Inner,
+            pair: (u16, u64),
+            #[endian_codec(q = 15)]
+            gain: f32,
+            #[endian_codec(hex)]
+            serial: u16,
+        }
+
+        let value = Example {
+            kind: Kind::Data,
+            id: 0x0102_0304,
+            name: *b"abc",
+            inner: Inner { a: -2, b: 7 },
+            pair: (0x0506, 0x0708_090a_0b0c_0d0e),
+            gain: 0.25,
+            serial: 0x1234,
+        };
+        let mut le = [0; Example::PACKED_LEN];
+        value.encode_as_le_bytes(&mut le);
+        let mut be = [0; Example::PACKED_LEN];
+        Example::decode_from_le_bytes(&le).encode_as_be_bytes(&mut be);
+
+        let mut swapped = le;
+        Example::swap_endian_in_buffer(&mut swapped);
+        assert_eq!(be, swapped);
+        Example::swap_endian_in_buffer(&mut swapped);
+        assert_eq!(le, swapped);
+    }
+
+    #[test]
+    fn resolve_relative_offset() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE)]
+        struct Header {
+            kind: u8,
+            #[endian_codec(offset_from = "names")]
+            names: u16,
+            #[endian_codec(offset_from = "kind")]
+            data: u8,
+        }
+
+        let buf = [1, 4, 0, 6, 0xAA, 0xBB, 0xCC, 0xDD];
+        let header = Header::decode_from_le_bytes(&buf[..Header::PACKED_LEN]);
+        assert_eq!(Some(&[0xBB, 0xCC, 0xDD][..]), header.resolve_names(&buf));
+        assert_eq!(Some(&[0xCC, 0xDD][..]), header.resolve_data(&buf));
+
+        let header = Header {
+            kind: 0,
+            names: 0xFFFF,
+            data: 8,
+        };
+        assert_eq!(None, header.resolve_names(&buf));
+        assert_eq!(Some(&[][..]), header.resolve_data(&buf));
+    }
+
+    #[test]
+    fn encode_returns_written_len() {
+        #[derive(PackedSize, EncodeLE, EncodeBE, EncodeME)]
+        struct Item {
+            #[endian = "be"]
+            id: u16,
+            flag: u8,
+        }
+
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE)]
+        struct Unit;
+
+        let items = [Item { id: 1, flag: 2 }, Item { id: 3, flag: 4 }];
+        let mut buf = [0; 2 * Item::PACKED_LEN + 1];
+        let mut off = 0;
+        for item in &items {
+            off += item.encode_as_le_bytes(&mut buf[off..off + Item::PACKED_LEN]);
+        }
+        off += 5u8.encode_as_be_bytes(&mut buf[off..]);
+        assert_eq!(buf.len(), off);
+        assert_eq!([1, 0, 2, 3, 0, 4, 5], buf);
+
+        assert_eq!(3, items[0].encode_as_be_bytes(&mut buf[..3]));
+        assert_eq!(3, items[0].encode_as_me_bytes(&mut buf[..3]));
+        assert_eq!(0, Unit.encode_as_le_bytes(&mut []));
+        assert_eq!(Unit, Unit::decode_from_le_bytes(&[]));
+    }
+
+    #[test]
+    fn try_encode() {
+        #[derive(PackedSize, EncodeLE, EncodeBE, EncodeME)]
+        struct Example {
+            #[endian = "le"]
+            a: u16,
+            b: u8,
+        }
+
+        let example = Example { a: 0x0102, b: 3 };
+        let mut buf = [0; 4];
+        assert_eq!(Ok(3), example.try_encode_as_le_bytes(&mut buf));
+        assert_eq!([2, 1, 3, 0], buf);
+        assert_eq!(Ok(3), example.try_encode_as_be_bytes(&mut buf[1..]));
+        assert_eq!([2, 1, 2, 3], buf);
+        assert_eq!(
+            Err(EndianError::BufferTooSmall { needed: 3, got: 2 }),
+            example.try_encode_as_me_bytes(&mut buf[..2])
+        );
+        assert_eq!([2, 1, 2, 3], buf);
+
+        // extra bytes are left alone on encode, but rejected on decode
+        assert_eq!(Ok(2), 0x0405_u16.try_encode_as_le_bytes(&mut buf));
+        assert_eq!([5, 4, 2, 3], buf);
+        assert_eq!(
+            Err(EndianError::BufferTooLarge { needed: 2, got: 4 }),
+            u16::try_decode_from_le_bytes(&buf)
+        );
+    }
+
+    #[test]
+    fn aligned_field() {
+        #[derive(
+            Debug,
+            PartialEq,
+            Eq,
+            PackedSize,
+            EncodeBE,
+            DecodeBE,
+            DecodeLE,
+            DecodeLEVersioned,
+            DebugLayout,
+        )]
+        struct Record {
+            kind: u8,
+            #[endian_codec(align_field = 4)]
+            len: u32,
+            flags: u8,
+        }
+
+        assert_eq!(9, Record::PACKED_LEN);
+        assert_eq!(4, Record::FIELDS[1].offset);
+
+        let record = Record {
+            kind: 1,
+            len: 0x0203_0405,
+            flags: 6,
+        };
+        let mut buf = [0xFF; Record::PACKED_LEN];
+        assert_eq!(9, record.encode_as_be_bytes(&mut buf));
+        assert_eq!([1, 0, 0, 0, 2, 3, 4, 5, 6], buf);
+        assert_eq!(record, Record::decode_from_be_bytes(&buf));
+        let le = [1, 0, 0, 0, 5, 4, 3, 2, 6];
+        assert_eq!(record, Record::decode_le_versioned(&le, 0).0);
+    }
+
+    #[test]
+    fn canonical_decode() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE, HashLE)]
+        struct Signed {
+            kind: u8,
+            #[endian_codec(align_field = 2)]
+            len: u16,
+        }
+
+        assert_eq!(
+            Ok(Signed { kind: 1, len: 2 }),
+            Signed::decode_le_canonical(&[1, 0, 2, 0])
+        );
+        // reserved padding byte is set
+        assert_eq!(
+            Err(EndianError::NonCanonical),
+            Signed::decode_le_canonical(&[1, 0xFF, 2, 0])
+        );
+        assert_eq!(
+            Err(EndianError::BufferTooSmall { needed: 4, got: 3 }),
+            Signed::decode_le_canonical(&[1, 0, 2])
+        );
+
+        // `None` is stored with a zeroed value
+        assert_eq!(Ok(None), Option::<u16>::decode_le_canonical(&[0, 0, 0]));
+        assert_eq!(
+            Err(EndianError::NonCanonical),
+            Option::<u16>::decode_le_canonical(&[0, 1, 0])
+        );
+
+        // no size limit
+        let mut big = [0; 2000];
+        big[1999] = 1;
+        assert_eq!(Ok(big), <[u8; 2000]>::decode_le_canonical(&big));
+        let padded = [1, 0, 7, 0, 0, 0];
+        assert_eq!(
+            Ok(PaddedArray::<u16, 2>::from_slice(&[7]).unwrap()),
+            PaddedArray::<u16, 2>::decode_le_canonical(&padded)
+        );
+        assert_eq!(
+            Err(EndianError::NonCanonical),
+            PaddedArray::<u16, 2>::decode_le_canonical(&[1, 0, 7, 0, 1, 0])
+        );
+    }
+
+    #[test]
+    fn large_byte_array() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeBE, DecodeBE)]
+        struct Signed {
+            len: u16,
+            mac: [u8; 64],
+        }
+
+        let signed = Signed {
+            len: 64,
+            mac: [0xAB; 64],
+        };
+        let mut buf = [0; 66];
+        assert_eq!(66, signed.encode_as_be_bytes(&mut buf));
+        assert_eq!([0, 64, 0xAB], buf[..3]);
+        assert_eq!(signed, Signed::decode_from_be_bytes(&buf));
+    }
+
+    #[test]
+    fn array_of_numbers() {
+        let samples: [u16; 3] = [0x0102, 0x0304, 0x0506];
+        let mut buf = [0; 6];
+        assert_eq!(6, samples.encode_as_le_bytes(&mut buf));
+        assert_eq!([2, 1, 4, 3, 6, 5], buf);
+        assert_eq!(samples, <[u16; 3]>::decode_from_le_bytes(&buf));
+        samples.encode_as_be_bytes(&mut buf);
+        assert_eq!([1, 2, 3, 4, 5, 6], buf);
+        assert_eq!(samples, <[u16; 3]>::decode_from_be_bytes(&buf));
+
+        <[u16; 3]>::swap_endian_in_buffer(&mut buf);
+        assert_eq!([2, 1, 4, 3, 6, 5], buf);
+
+        let words: [[u32; 2]; 2] = [[1, 2], [3, 4]];
+        let mut buf = [0; 16];
+        words.encode_as_be_bytes(&mut buf);
+        assert_eq!(3, buf[11]);
+        assert_eq!(words, <[[u32; 2]; 2]>::decode_from_be_bytes(&buf));
+
+        assert_eq!(0, [(); 4].encode_as_le_bytes(&mut []));
+    }
+
+    #[test]
+    fn wire_order() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, DebugLayout)]
+        #[endian_codec(wire_order("c", "a", "b"))]
+        struct Reordered {
+            a: u16,
+            b: u8,
+            c: u32,
+        }
+
+        let value = Reordered {
+            a: 0x0102,
+            b: 3,
+            c: 0x0405_0607,
+        };
+        let mut buf = [0; Reordered::PACKED_LEN];
+        assert_eq!(7, value.encode_as_le_bytes(&mut buf));
+        assert_eq!([7, 6, 5, 4, 2, 1, 3], buf);
+        assert_eq!(value, Reordered::decode_from_le_bytes(&buf));
+        assert_eq!(
+            ("a", 4),
+            (Reordered::FIELDS[1].name, Reordered::FIELDS[1].offset)
+        );
+    }
+
+    #[test]
+    fn floats() {
+        let mut buf = [0; 4];
+        1.5f32.encode_as_le_bytes(&mut buf);
+        assert_eq!([0, 0, 0xC0, 0x3F], buf);
+        assert_eq!(1.5, f32::decode_from_le_bytes(&buf));
+
+        let mut buf = [0; 8];
+        (-2.0f64).encode_as_be_bytes(&mut buf);
+        assert_eq!([0xC0, 0, 0, 0, 0, 0, 0, 0], buf);
+        assert_eq!(-2.0, f64::decode_from_be_bytes(&buf));
+
+        // signalling NaN with a payload
+        let nan = f32::from_bits(0x7F80_0001);
+        let mut buf = [0; 4];
+        nan.encode_as_be_bytes(&mut buf);
+        assert_eq!(0x7F80_0001, f32::decode_from_be_bytes(&buf).to_bits());
+    }
+
+    #[test]
+    fn duration_samples() {
+        use core::time::Duration;
+
+        let samples = [
+            Duration::new(1, 2),
+            Duration::from_millis(1500),
+            Duration::new(u64::MAX, 999_999_999),
+        ];
+        let mut buf = [0; 36];
+        assert_eq!(36, samples.encode_as_le_bytes(&mut buf));
+        assert_eq!([1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0], buf[..12]);
+        assert_eq!(
+            [1, 0, 0, 0, 0, 0, 0, 0, 0x00, 0x65, 0xCD, 0x1D],
+            buf[12..24]
+        );
+        assert_eq!([0xFF; 8], buf[24..32]);
+        assert_eq!(999_999_999u32.to_le_bytes(), buf[32..]);
+        assert_eq!(samples, <[Duration; 3]>::decode_from_le_bytes(&buf));
+
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeBE, DecodeBE)]
+        struct Timing {
+            id: u8,
+            samples: [Duration; 2],
+        }
+
+        let timing = Timing {
+            id: 7,
+            samples: [Duration::from_secs(3), Duration::from_nanos(4)],
+        };
+        let mut buf = [0; 25];
+        timing.encode_as_be_bytes(&mut buf);
+        assert_eq!(3, buf[8]);
+        assert_eq!(4, buf[24]);
+        assert_eq!(timing, Timing::decode_from_be_bytes(&buf));
+    }
+
+    #[test]
+    fn duration_invalid_nanos() {
+        use core::time::Duration;
+
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            Duration::try_decode_from_le_bytes(&[0xff; 12])
+        );
+        let mut buf = [0; 12];
+        buf[8..].copy_from_slice(&1_000_000_000u32.to_be_bytes());
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            Duration::try_decode_from_be_bytes(&buf)
+        );
+        buf[8..].copy_from_slice(&999_999_999u32.to_be_bytes());
+        assert_eq!(
+            Ok(Duration::new(0, 999_999_999)),
+            Duration::try_decode_from_be_bytes(&buf)
+        );
+    }
+
+    #[test]
+    fn bool_flags() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeME, DecodeME)]
+        #[endian(le)]
+        struct Config {
+            enabled: bool,
+            retries: u16,
+            verbose: bool,
+        }
+
+        let config = Config {
+            enabled: true,
+            retries: 3,
+            verbose: false,
+        };
+        let mut buf = [0; 4];
+        config.encode_as_le_bytes(&mut buf);
+        assert_eq!([1, 3, 0, 0], buf);
+        assert_eq!(config, Config::decode_from_le_bytes(&buf));
+        assert_eq!(config, Config::decode_from_me_bytes(&buf));
+        // any non-zero byte is true
+        assert!(bool::decode_from_be_bytes(&[0x80]));
+    }
+
+    #[test]
+    fn trailing_records() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeBE)]
+        struct Header {
+            kind: u8,
+            count: u16,
+        }
+
+        let frame = [7, 0, 3, 0, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 0xFF];
+        let (header, samples) = decode_trailing_be::<Header, u32>(&frame).unwrap();
+        assert_eq!(Header { kind: 7, count: 3 }, header);
+        assert_eq!(usize::from(header.count), samples.len());
+        assert_eq!([0xFF], samples.remainder());
+        assert_eq!(1 + 0x100 + 0x1_0000, samples.sum::<u32>());
+        assert_eq!(frame.len() - 1, trailing_len::<Header, u32>(3));
+        assert_eq!(4 + 4 * 2, trailing_len::<u32, u16>(4));
+
+        assert_eq!(
+            Err(EndianError::BufferTooSmall { needed: 3, got: 2 }),
+            decode_trailing_be::<Header, u32>(&frame[..2]).map(|(header, _)| header)
+        );
     }
-}
 
-impl DecodeME for u8 {
-    #[inline]
-    fn decode_from_me_bytes(bytes: &[u8]) -> Self {
-        let mut arr = [0; 1];
-        arr.copy_from_slice(bytes);
-        Self::from_le_bytes(arr)
+    #[test]
+    #[should_panic(expected = "field offset overflows usize")]
+    fn trailing_len_overflow() {
+        trailing_len::<u8, u64>(usize::MAX / 4);
     }
-}
 
-impl_codec_for_primitives!(u16, 2);
-impl_codec_for_primitives!(i16, 2);
-impl_codec_for_primitives!(u32, 4);
-impl_codec_for_primitives!(i32, 4);
-impl_codec_for_primitives!(u64, 8);
-impl_codec_for_primitives!(i64, 8);
-impl_codec_for_primitives!(u128, 16);
-impl_codec_for_primitives!(i128, 16);
+    #[test]
+    fn chars() {
+        let mut buf = [0; 4];
+        'é'.encode_as_le_bytes(&mut buf);
+        assert_eq!([0xE9, 0, 0, 0], buf);
+        assert_eq!('é', char::decode_from_le_bytes(&buf));
+        '😀'.encode_as_be_bytes(&mut buf);
+        assert_eq!([0, 1, 0xF6, 0x00], buf);
+        assert_eq!(Ok('😀'), char::try_decode_from_be_bytes(&buf));
 
-macro_rules! impl_codec_for_array {
-    ($type:ty, $size:expr) => {
-        impl PackedSize for $type {
-            const PACKED_LEN: usize = $size;
+        // surrogate
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            char::try_decode_from_le_bytes(&[0x00, 0xD8, 0, 0])
+        );
+        assert_eq!(
+            Err(EndianError::BufferTooSmall { needed: 4, got: 1 }),
+            char::try_decode_from_le_bytes(&[0x41])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid char value")]
+    fn invalid_char() {
+        char::decode_from_be_bytes(&[0, 0x11, 0, 0]);
+    }
+
+    #[test]
+    fn pointer_sized() {
+        assert_eq!(8, usize::PACKED_LEN);
+        assert_eq!(8, isize::PACKED_LEN);
+
+        let mut buf = [0; 8];
+        0x0102usize.encode_as_be_bytes(&mut buf);
+        assert_eq!([0, 0, 0, 0, 0, 0, 1, 2], buf);
+        assert_eq!(Ok(0x0102), usize::try_decode_from_be_bytes(&buf));
+        (-2isize).encode_as_le_bytes(&mut buf);
+        assert_eq!([0xFE, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF], buf);
+        assert_eq!(-2, isize::decode_from_le_bytes(&buf));
+
+        let big = u64::MAX.to_le_bytes();
+        let expected = usize::try_from(u64::MAX).map_err(|_| EndianError::InvalidValue);
+        assert_eq!(expected, usize::try_decode_from_le_bytes(&big));
+    }
+
+    #[test]
+    fn nested_forced_little_endian() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeME, DecodeME)]
+        struct Inner {
+            #[endian = "be"]
+            a: u16,
+            #[endian = "le"]
+            b: u16,
         }
 
-        impl EncodeBE for $type {
-            #[inline]
-            fn encode_as_be_bytes(&self, bytes: &mut [u8]) {
-                bytes.copy_from_slice(self);
-            }
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeME, DecodeME)]
+        struct Outer {
+            // without an attribute the field uses its own mixed-endian layout
+            mixed: Inner,
+            #[endian = "le"]
+            little: Inner,
         }
 
-        impl EncodeLE for $type {
-            #[inline]
-            fn encode_as_le_bytes(&self, bytes: &mut [u8]) {
-                bytes.copy_from_slice(self);
-            }
+        let inner = Inner {
+            a: 0x0102,
+            b: 0x0304,
+        };
+        let outer = Outer {
+            mixed: Inner {
+                a: 0x0102,
+                b: 0x0304,
+            },
+            little: inner,
+        };
+        let mut buf = [0; 8];
+        outer.encode_as_me_bytes(&mut buf);
+        assert_eq!([1, 2, 4, 3, 2, 1, 4, 3], buf);
+        assert_eq!(outer, Outer::decode_from_me_bytes(&buf));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn encode_to_vec() {
+        #[derive(PackedSize, EncodeLE, EncodeBE, EncodeME)]
+        struct Header {
+            #[endian = "le"]
+            kind: u16,
+            #[endian = "be"]
+            len: u16,
         }
 
-        impl EncodeME for $type {
-            #[inline]
-            fn encode_as_me_bytes(&self, bytes: &mut [u8]) {
-                bytes.copy_from_slice(self);
-            }
+        let header = Header {
+            kind: 0x0102,
+            len: 0x0304,
+        };
+        assert_eq!(alloc::vec![2, 1, 4, 3], header.encode_to_le_vec());
+        assert_eq!(alloc::vec![1, 2, 3, 4], header.encode_to_be_vec());
+        assert_eq!(alloc::vec![2, 1, 3, 4], header.encode_to_me_vec());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn skipped_string_field() {
+        use alloc::string::String;
+
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE)]
+        struct Device {
+            id: u32,
+            #[endian(skip)]
+            name: String,
         }
 
-        impl DecodeBE for $type {
-            #[inline]
-            fn decode_from_be_bytes(bytes: &[u8]) -> Self {
-                let mut arr = [0; Self::PACKED_LEN];
-                arr.copy_from_slice(bytes);
-                arr
-            }
+        assert_eq!(4, Device::PACKED_LEN);
+        let device = Device {
+            id: 0x0102_0304,
+            name: String::from("sensor"),
+        };
+        let mut bytes = [0; 4];
+        device.encode_as_le_bytes(&mut bytes);
+        assert_eq!([4, 3, 2, 1], bytes);
+        assert_eq!(
+            Device {
+                id: 0x0102_0304,
+                name: String::new(),
+            },
+            Device::decode_from_le_bytes(&bytes)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn collect_field_errors() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE)]
+        #[repr(u8)]
+        enum Kind {
+            Ping = 1,
         }
 
-        impl DecodeLE for $type {
-            #[inline]
-            fn decode_from_le_bytes(bytes: &[u8]) -> Self {
-                let mut arr = [0; Self::PACKED_LEN];
-                arr.copy_from_slice(bytes);
-                arr
-            }
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE)]
+        struct Header {
+            kind: Kind,
+            len: u16,
         }
 
-        impl DecodeME for $type {
-            #[inline]
-            fn decode_from_me_bytes(bytes: &[u8]) -> Self {
-                let mut arr = [0; Self::PACKED_LEN];
-                arr.copy_from_slice(bytes);
-                arr
-            }
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE)]
+        struct Frame {
+            header: Header,
+            letter: char,
         }
-    };
-}
 
-impl_codec_for_array!([u8; 1], 1);
-impl_codec_for_array!([u8; 2], 2);
-impl_codec_for_array!([u8; 3], 3);
-impl_codec_for_array!([u8; 4], 4);
-impl_codec_for_array!([u8; 5], 5);
-impl_codec_for_array!([u8; 6], 6);
-impl_codec_for_array!([u8; 7], 7);
-impl_codec_for_array!([u8; 8], 8);
-impl_codec_for_array!([u8; 9], 9);
-impl_codec_for_array!([u8; 10], 10);
-impl_codec_for_array!([u8; 11], 11);
-impl_codec_for_array!([u8; 12], 12);
-impl_codec_for_array!([u8; 13], 13);
-impl_codec_for_array!([u8; 14], 14);
-impl_codec_for_array!([u8; 15], 15);
-impl_codec_for_array!([u8; 16], 16);
-impl_codec_for_array!([u8; 17], 17);
-impl_codec_for_array!([u8; 18], 18);
-impl_codec_for_array!([u8; 19], 19);
-impl_codec_for_array!([u8; 20], 20);
-impl_codec_for_array!([u8; 21], 21);
-impl_codec_for_array!([u8; 22], 22);
-impl_codec_for_array!([u8; 23], 23);
-impl_codec_for_array!([u8; 24], 24);
-impl_codec_for_array!([u8; 25], 25);
-impl_codec_for_array!([u8; 26], 26);
-impl_codec_for_array!([u8; 27], 27);
-impl_codec_for_array!([u8; 28], 28);
-impl_codec_for_array!([u8; 29], 29);
-impl_codec_for_array!([u8; 30], 30);
-impl_codec_for_array!([u8; 31], 31);
-impl_codec_for_array!([u8; 32], 32);
+        let valid = [1, 4, 0, 0x41, 0, 0, 0];
+        assert_eq!(
+            Ok(Frame {
+                header: Header {
+                    kind: Kind::Ping,
+                    len: 4
+                },
+                letter: 'A',
+            }),
+            Frame::decode_le_collect_errors(&valid)
+        );
+
+        let errors = Frame::decode_le_collect_errors(&[9, 4, 0, 0, 0xD8, 0, 0]).unwrap_err();
+        let fields: Vec<_> = errors.iter().map(|err| err.field.as_str()).collect();
+        assert_eq!(vec!["header.kind", "letter"], fields);
+        assert!(errors
+            .iter()
+            .all(|err| err.error == EndianError::InvalidValue));
+        assert_eq!(
+            "`header.kind`: bytes hold an invalid value",
+            std::format!("{}", errors[0])
+        );
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        // only the first error, with the path through nested structs
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE)]
+        struct Packet {
+            seq: u8,
+            frame: Frame,
+        }
+
+        assert_eq!(
+            Err(FieldError {
+                field: "frame.header.kind".into(),
+                error: EndianError::InvalidValue,
+            }),
+            Packet::decode_le_with_path(&[7, 9, 4, 0, 0, 0xD8, 0, 0])
+        );
+        assert_eq!(
+            Err(FieldError {
+                field: "frame.letter".into(),
+                error: EndianError::InvalidValue,
+            }),
+            Packet::decode_le_with_path(&[7, 1, 4, 0, 0, 0xD8, 0, 0])
+        );
+        assert!(Packet::decode_le_with_path(&[7, 1, 4, 0, 0x41, 0, 0, 0]).is_ok());
+        // errors of the whole value have an empty path
+        assert_eq!(
+            Err(FieldError {
+                field: "".into(),
+                error: EndianError::BufferTooSmall { needed: 8, got: 7 },
+            }),
+            Packet::decode_le_with_path(&[7, 1, 4, 0, 0x41, 0, 0])
+        );
+
+        let errors = Frame::decode_le_collect_errors(&valid[1..]).unwrap_err();
+        assert_eq!(
+            EndianError::BufferTooSmall { needed: 7, got: 6 },
+            errors[0].error
+        );
+
+        // fields with a different wire form report their errors too
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE)]
+        struct Tagged {
+            #[endian_codec(hex)]
+            id: u16,
+            flags: u8,
+        }
+
+        assert_eq!(
+            Ok(Tagged { id: 0x12, flags: 1 }),
+            Tagged::decode_le_collect_errors(b"1200\x01")
+        );
+        let errors = Tagged::decode_le_collect_errors(b"zz00\x01").unwrap_err();
+        assert_eq!(
+            vec![FieldError {
+                field: "id".into(),
+                error: EndianError::InvalidValue,
+            }],
+            errors
+        );
+    }
+
+    #[test]
+    fn native_endian() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeNE, DecodeNE)]
+        struct Shared {
+            seq: u32,
+            flags: [u16; 2],
+            ready: bool,
+        }
+
+        let shared = Shared {
+            seq: 0x0102_0304,
+            flags: [5, 6],
+            ready: true,
+        };
+        let mut buf = [0; Shared::PACKED_LEN];
+        assert_eq!(9, shared.encode_as_ne_bytes(&mut buf));
+        assert_eq!(0x0102_0304u32.to_ne_bytes(), buf[..4]);
+        assert_eq!(6u16.to_ne_bytes(), buf[6..8]);
+        assert_eq!(shared, Shared::decode_from_ne_bytes(&buf));
+        assert_eq!(Ok(shared), Shared::try_decode_from_ne_bytes(&buf));
+
+        // a native-endian field inside a mixed-endian struct
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeME, DecodeME)]
+        struct Slot {
+            #[endian = "be"]
+            id: u16,
+            #[endian = "native"]
+            seq: u32,
+            #[endian = "ne"]
+            flags: u16,
+        }
+
+        let slot = Slot {
+            id: 1,
+            seq: 0x0102_0304,
+            flags: 5,
+        };
+        let mut buf = [0; Slot::PACKED_LEN];
+        slot.encode_as_me_bytes(&mut buf);
+        assert_eq!([0, 1], buf[..2]);
+        assert_eq!(0x0102_0304u32.to_ne_bytes(), buf[2..6]);
+        assert_eq!(5u16.to_ne_bytes(), buf[6..]);
+        assert_eq!(slot, Slot::decode_from_me_bytes(&buf));
+    }
+
+    #[test]
+    fn length_prefixed_frame() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeBE, DecodeBE)]
+        struct Reading {
+            sensor: u8,
+            value: u16,
+        }
+
+        let readings = [
+            Reading {
+                sensor: 1,
+                value: 0x0203,
+            },
+            Reading {
+                sensor: 4,
+                value: 0x0506,
+            },
+        ];
+        let mut frame = [0xFF; 32];
+        let written = encode_length_prefixed_be::<u32, _>(&mut frame, |body| {
+            readings
+                .iter()
+                .zip(body.chunks_exact_mut(Reading::PACKED_LEN))
+                .map(|(reading, bytes)| reading.encode_as_be_bytes(bytes))
+                .sum()
+        });
+        assert_eq!(10, written);
+        assert_eq!([0, 0, 0, 6, 1, 2, 3, 4, 5, 6], frame[..written]);
+
+        let (len, records) = decode_trailing_be::<u32, Reading>(&frame[..written]).unwrap();
+        assert_eq!(6, len);
+        assert!(records.eq(readings));
+    }
+
+    #[test]
+    #[should_panic(expected = "body length 300 doesn't fit into the prefix")]
+    fn length_prefix_overflow() {
+        let mut frame = [0; 301];
+        encode_length_prefixed_le::<u8, _>(&mut frame, |body| body.len());
+    }
+
+    #[test]
+    fn char_array() {
+        let text = ['a', 'ż', '€', '😀'];
+        let bytes = roundtrip!([char; 4], text, be);
+        assert_eq!([0, 0, 0x01, 0x7C], bytes[4..8]);
+        let bytes = roundtrip!([char; 4], text, le);
+        assert_eq!([0x00, 0xF6, 0x01, 0x00], bytes[12..]);
+        assert_eq!(Ok(text), <[char; 4]>::try_decode_from_le_bytes(&bytes));
+
+        let mut invalid = bytes;
+        invalid[8..12].copy_from_slice(&0xD800u32.to_le_bytes());
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            <[char; 4]>::try_decode_from_le_bytes(&invalid)
+        );
+    }
+
+    #[test]
+    fn struct_level_endian_name_value() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeME, DecodeME)]
+        #[endian = "be"]
+        struct Mostly {
+            a: u16,
+            #[endian = "le"]
+            b: u16,
+            c: u32,
+        }
+
+        roundtrip!(
+            Mostly,
+            Mostly {
+                a: 0x0102,
+                b: 0x0304,
+                c: 0x0506_0708
+            },
+            me,
+            [1, 2, 4, 3, 5, 6, 7, 8]
+        );
+    }
+
+    #[test]
+    fn nested_mixed_endian() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeME, DecodeME)]
+        struct Record {
+            #[endian = "le"]
+            id: u16,
+            #[endian = "be"]
+            len: u16,
+        }
+
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeME, DecodeME)]
+        #[endian = "be"]
+        struct Message {
+            kind: u16,
+            #[endian = "me"]
+            first: Record,
+            #[endian = "mixed"]
+            second: Record,
+        }
+
+        roundtrip!(
+            Message,
+            Message {
+                kind: 0x0102,
+                first: Record {
+                    id: 0x0304,
+                    len: 0x0506
+                },
+                second: Record {
+                    id: 0x0708,
+                    len: 0x090A
+                },
+            },
+            me,
+            [1, 2, 4, 3, 5, 6, 8, 7, 9, 10]
+        );
+    }
+
+    #[test]
+    fn endian_sensitive() {
+        #[derive(PackedSize)]
+        struct Digest {
+            _a: [u8; 16],
+            _b: u8,
+        }
+
+        #[derive(PackedSize)]
+        struct Header {
+            _a: [u8; 2],
+            _b: u16,
+        }
+
+        const _: () = assert!(!Digest::ENDIAN_SENSITIVE);
+        const _: () = assert!(Header::ENDIAN_SENSITIVE);
+        const _: () = assert!(!<[Digest; 2]>::ENDIAN_SENSITIVE);
+        const _: () = assert!(<[Header; 1]>::ENDIAN_SENSITIVE);
+    }
+
+    #[test]
+    fn tuple_struct() {
+        #[derive(
+            Debug,
+            PartialEq,
+            Eq,
+            PackedSize,
+            EncodeLE,
+            DecodeLE,
+            EncodeBE,
+            DecodeBE,
+            EncodeME,
+            DecodeME,
+        )]
+        struct Pair(#[endian = "le"] u16, #[endian = "be"] u32);
+
+        roundtrip!(Pair, Pair(0x0102, 0x0304_0506), le, [2, 1, 6, 5, 4, 3]);
+        roundtrip!(Pair, Pair(0x0102, 0x0304_0506), be, [1, 2, 3, 4, 5, 6]);
+        roundtrip!(Pair, Pair(0x0102, 0x0304_0506), me, [2, 1, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn pod_decode() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PackedSize, EncodeNE, DecodeNE)]
+        #[repr(C)]
+        struct Sample {
+            time: u32,
+            channel: u16,
+            flags: [u8; 2],
+        }
+
+        unsafe impl Pod for Sample {}
+
+        let sample = Sample {
+            time: 0x0102_0304,
+            channel: 0x0506,
+            flags: [7, 8],
+        };
+        let mut bytes = [0; Sample::PACKED_LEN + 1];
+        sample.encode_as_ne_bytes(&mut bytes[..Sample::PACKED_LEN]);
+        assert_eq!(
+            sample,
+            decode_ne_pod::<Sample>(&bytes[..Sample::PACKED_LEN])
+        );
+        assert_eq!(
+            Sample::decode_from_ne_bytes(&bytes[..Sample::PACKED_LEN]),
+            decode_ne_pod::<Sample>(&bytes[..Sample::PACKED_LEN])
+        );
+
+        // one of the two slices is misaligned for `u32`
+        let casts = [
+            cast_ne_pod::<Sample>(&bytes[..Sample::PACKED_LEN]),
+            cast_ne_pod::<Sample>(&bytes[1..]),
+        ];
+        assert_eq!(1, casts.iter().filter(|cast| cast.is_none()).count());
+        assert_eq!(None, cast_ne_pod::<Sample>(&bytes));
+
+        let unaligned = decode_ne_pod::<Sample>(&bytes[1..]);
+        assert_eq!(Sample::decode_from_ne_bytes(&bytes[1..]), unaligned);
+    }
+
+    #[test]
+    #[should_panic(expected = "Pod type has padding")]
+    fn pod_decode_padded() {
+        #[derive(Clone, Copy, PackedSize)]
+        #[repr(C)]
+        struct Padded {
+            _a: u8,
+            _b: u16,
+        }
+
+        unsafe impl Pod for Padded {}
+
+        decode_ne_pod::<Padded>(&[0; Padded::PACKED_LEN]);
+    }
+
+    #[test]
+    fn codec_marker_traits() {
+        fn roundtrip_le<T: CodecLE>(value: &T) -> T {
+            let mut buf = [0; 16];
+            value.encode_as_le_bytes(&mut buf[..T::PACKED_LEN]);
+            T::decode_from_le_bytes(&buf[..T::PACKED_LEN])
+        }
+
+        fn roundtrip_be<T: CodecBE>(value: &T) -> T {
+            let mut buf = [0; 16];
+            value.encode_as_be_bytes(&mut buf[..T::PACKED_LEN]);
+            T::decode_from_be_bytes(&buf[..T::PACKED_LEN])
+        }
+
+        fn roundtrip_me<T: CodecME>(value: &T) -> T {
+            let mut buf = [0; 16];
+            value.encode_as_me_bytes(&mut buf[..T::PACKED_LEN]);
+            T::decode_from_me_bytes(&buf[..T::PACKED_LEN])
+        }
+
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeME, DecodeME)]
+        struct Header {
+            #[endian = "le"]
+            kind: u16,
+            #[endian = "be"]
+            len: u32,
+        }
+
+        assert_eq!(0x0102_0304, roundtrip_le(&0x0102_0304u32));
+        assert_eq!(-7i16, roundtrip_be(&-7i16));
+        let header = Header { kind: 1, len: 2 };
+        assert_eq!(header, roundtrip_me(&header));
+    }
+
+    #[test]
+    fn array_of_mixed_endian_structs() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeME, DecodeME)]
+        struct Header {
+            #[endian = "le"]
+            id: u16,
+            #[endian = "be"]
+            len: u16,
+        }
+
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeME, DecodeME)]
+        struct Telemetry {
+            headers: [Header; 3],
+        }
+
+        roundtrip!(
+            Telemetry,
+            Telemetry {
+                headers: [
+                    Header {
+                        id: 0x0102,
+                        len: 0x0304
+                    },
+                    Header {
+                        id: 0x0506,
+                        len: 0x0708
+                    },
+                    Header {
+                        id: 0x090A,
+                        len: 0x0B0C
+                    },
+                ],
+            },
+            me,
+            [2, 1, 3, 4, 6, 5, 7, 8, 0x0A, 9, 0x0B, 0x0C]
+        );
+    }
+
+    #[test]
+    fn encode_decode_at() {
+        #[derive(
+            Debug,
+            PartialEq,
+            Eq,
+            PackedSize,
+            EncodeLE,
+            DecodeLE,
+            EncodeBE,
+            DecodeBE,
+            EncodeME,
+            DecodeME,
+        )]
+        #[endian(le)]
+        struct Header {
+            kind: u8,
+            len: u16,
+        }
+
+        let headers = [
+            Header { kind: 1, len: 2 },
+            Header {
+                kind: 3,
+                len: 0x0405,
+            },
+        ];
+        let mut buf = [0; 2 * Header::PACKED_LEN + 1];
+        let mut offset = 1;
+        for header in &headers {
+            offset = header.encode_as_le_bytes_at(&mut buf, offset);
+        }
+        assert_eq!(buf.len(), offset);
+        assert_eq!([0, 1, 2, 0, 3, 5, 4], buf);
+
+        let (first, offset) = Header::decode_from_le_bytes_at(&buf, 1);
+        let (second, offset) = Header::decode_from_le_bytes_at(&buf, offset);
+        assert_eq!(headers, [first, second]);
+        assert_eq!(buf.len(), offset);
+
+        assert_eq!(4, headers[1].encode_as_be_bytes_at(&mut buf, 1));
+        assert_eq!([0, 3, 4, 5], buf[..4]);
+        assert_eq!(
+            (
+                Header {
+                    kind: 3,
+                    len: 0x0405
+                },
+                4
+            ),
+            Header::decode_from_be_bytes_at(&buf, 1)
+        );
+
+        assert_eq!(3, headers[0].encode_as_me_bytes_at(&mut buf, 0));
+        assert_eq!([1, 2, 0], buf[..3]);
+        assert_eq!(
+            (Header { kind: 1, len: 2 }, 3),
+            Header::decode_from_me_bytes_at(&buf, 0)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "buffer holds fewer than offset + PACKED_LEN bytes: 4 < 3 + 2")]
+    fn encode_at_short_buffer() {
+        0x0102_u16.encode_as_le_bytes_at(&mut [0; 4], 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "buffer holds fewer than offset + PACKED_LEN bytes")]
+    fn decode_at_offset_overflow() {
+        u16::decode_from_be_bytes_at(&[0; 4], usize::MAX);
+    }
+
+    #[test]
+    fn decode_le_hybrid() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE, DecodeLEHybrid)]
+        struct Packet {
+            kind: u16,
+            #[endian_codec(borrow)]
+            payload: [u8; 8],
+            crc: u32,
+        }
+
+        let mut bytes = [0; Packet::PACKED_LEN];
+        bytes[..2].copy_from_slice(&[1, 2]);
+        bytes[2..10].copy_from_slice(b"payload!");
+        bytes[10..].copy_from_slice(&[3, 4, 5, 6]);
+
+        let packet = PacketHybrid::decode_from_le_bytes(&bytes);
+        assert_eq!(0x0201, packet.kind);
+        assert_eq!(b"payload!", packet.payload);
+        assert_eq!(0x0605_0403, packet.crc);
+        // the payload aliases the buffer, scalars are copies
+        assert!(core::ptr::eq(packet.payload.as_ptr(), bytes[2..].as_ptr()));
+        assert!(!core::ptr::eq(
+            &packet.kind as *const u16 as *const u8,
+            bytes.as_ptr()
+        ));
+
+        let owned = Packet {
+            kind: packet.kind,
+            payload: *packet.payload,
+            crc: packet.crc,
+        };
+        assert_eq!(Packet::decode_from_le_bytes(&bytes), owned);
+
+        #[derive(PackedSize, DecodeLE, DecodeLEHybrid)]
+        struct Blob {
+            len: u8,
+            #[borrow]
+            data: [u8; 4],
+        }
+
+        let bytes = [4, 1, 2, 3, 4];
+        let blob = BlobHybrid::decode_from_le_bytes(&bytes);
+        assert_eq!(4, blob.len);
+        assert!(core::ptr::eq(blob.data.as_ptr(), bytes[1..].as_ptr()));
+        let owned = Blob::decode_from_le_bytes(&bytes);
+        assert_eq!((owned.len, owned.data), (blob.len, *blob.data));
+    }
+
+    #[test]
+    fn large_struct_offsets() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeME, DecodeME)]
+        #[endian(be)]
+        struct Large {
+            head: u16,
+            blob: [[u8; 4096]; 16],
+            #[endian_codec(align_field = 4096)]
+            tail: u32,
+        }
+
+        // `tail` is moved from 2 + 16 * 4096 to the next multiple of 4096
+        assert_eq!(17 * 4096 + 4, Large::PACKED_LEN);
+        let large = std::boxed::Box::new(Large {
+            head: 0x0102,
+            blob: [[7; 4096]; 16],
+            tail: 0x0304_0506,
+        });
+        let mut bytes = std::vec![0xff; Large::PACKED_LEN];
+        large.encode_as_le_bytes(&mut bytes);
+        assert_eq!([2, 1], bytes[..2]);
+        assert!(bytes[2..16 * 4096 + 2].iter().all(|&b| b == 7));
+        assert!(bytes[16 * 4096 + 2..17 * 4096].iter().all(|&b| b == 0));
+        assert_eq!([6, 5, 4, 3], bytes[17 * 4096..]);
+        assert_eq!(*large, Large::decode_from_le_bytes(&bytes));
+
+        large.encode_as_me_bytes(&mut bytes);
+        assert_eq!([1, 2], bytes[..2]);
+        assert_eq!([3, 4, 5, 6], bytes[17 * 4096..]);
+        assert_eq!(*large, Large::decode_from_me_bytes(&bytes));
+    }
 
     #[test]
-    fn derive_endian_size() {
-        #[derive(PackedSize)]
-        struct A {};
-        assert_eq!(A::PACKED_LEN, 0);
+    #[should_panic(expected = "field offset overflows usize")]
+    fn field_end_overflow() {
+        field_end(usize::MAX - 1, 2);
+    }
 
-        #[derive(PackedSize)]
-        struct B {
-            _a: u16,
+    #[test]
+    fn encode_array() {
+        #[derive(PackedSize, EncodeLE, EncodeBE, EncodeME)]
+        #[endian(le)]
+        struct Header {
+            kind: u8,
+            #[endian = "be"]
+            len: u16,
         }
-        assert_eq!(B::PACKED_LEN, 2);
 
-        #[derive(PackedSize)]
-        struct C {
-            _a: u16,
-            _b: u16,
-        }
-        assert_eq!(C::PACKED_LEN, 2 + 2);
+        let header = Header {
+            kind: 1,
+            len: 0x0203,
+        };
+        assert_eq!([1, 3, 2], header.encode_le_array());
+        assert_eq!([1, 2, 3], header.encode_be_array());
+        assert_eq!([1, 2, 3], header.encode_me_array());
     }
 
     #[test]
-    fn derive_littlendian_serialize() {
-        #[derive(PackedSize, EncodeLE)]
-        struct Example {
-            a: u16,
+    fn option_presence_byte() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE)]
+        struct Reading {
+            id: u8,
+            value: Option<u16>,
         }
 
-        let t = Example { a: 5 };
-        let mut b = [0; 2];
-        t.encode_as_le_bytes(&mut b);
+        let some = Reading {
+            id: 1,
+            value: Some(0x0203),
+        };
+        roundtrip!(Reading, some, le, [1, 1, 3, 2]);
+        let some = Reading {
+            id: 1,
+            value: Some(0x0203),
+        };
+        roundtrip!(Reading, some, be, [1, 1, 2, 3]);
+        roundtrip!(Reading, Reading { id: 4, value: None }, le, [4, 0, 0, 0]);
+
+        assert_eq!(
+            Ok(None),
+            Option::<u16>::try_decode_from_le_bytes(&[0, 9, 9])
+        );
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            Option::<u16>::try_decode_from_le_bytes(&[2, 0, 0])
+        );
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            Option::<char>::try_decode_from_le_bytes(&[1, 0, 0xD8, 0, 0])
+        );
     }
 
     #[test]
-    fn derive_bigendian_serialize() {
-        #[derive(PackedSize, EncodeBE)]
-        struct Example {
-            a: u16,
-        }
+    fn option_non_zero_niche() {
+        let some = NonZeroU16::new(0x0102);
+        assert_eq!(2, Option::<NonZeroU16>::PACKED_LEN);
+        roundtrip!(Option<NonZeroU16>, some, le, [2, 1]);
+        roundtrip!(Option<NonZeroU16>, some, be, [1, 2]);
+        roundtrip!(Option<NonZeroU16>, None, le, [0, 0]);
+        roundtrip!(Option<NonZeroU16>, None, be, [0, 0]);
+    }
 
-        let t = Example { a: 5 };
-        let mut b = [0; 2];
-        t.encode_as_be_bytes(&mut b);
+    #[test]
+    fn array_of_options() {
+        type Slots = [Option<u16>; 3];
+        assert_eq!(3 * (1 + 2), Slots::PACKED_LEN);
+        let slots = [Some(0x0102), None, Some(7)];
+        roundtrip!(Slots, slots, le, [1, 2, 1, 0, 0, 0, 1, 7, 0]);
+        roundtrip!(Slots, slots, be, [1, 1, 2, 0, 0, 0, 1, 0, 7]);
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            Slots::try_decode_from_le_bytes(&[1, 2, 1, 3, 0, 0, 1, 7, 0])
+        );
     }
 
     #[test]
-    fn derive_mixed_endian_serialize() {
-        #[derive(PackedSize, EncodeME, Default)]
-        struct Example {
-            #[endian = "le"]
-            a: u16,
-            #[endian = "be"]
-            b: u16,
-            #[endian = "little"]
-            aa: i16,
-            #[endian = "big"]
-            bb: i16,
-        }
+    fn check_len_matches() {
+        const LEN: usize = check_len::<(u8, [u16; 2])>(5);
+        assert_eq!(5, [0u8; LEN].len());
+    }
 
-        let t = Example::default();
-        let mut b = [0; 8];
-        t.encode_as_me_bytes(&mut b);
+    #[test]
+    #[should_panic(expected = "length differs from PACKED_LEN")]
+    fn check_len_differs() {
+        check_len::<u64>(4);
     }
 
     #[test]
-    fn derive_all_serialize() {
-        #[derive(Default, PackedSize, EncodeLE, EncodeBE, EncodeME)]
-        struct Example {
-            #[endian = "be"]
-            a: u16,
-            b: [u8; 32],
+    fn encode_to_byte_sink() {
+        struct Xor {
+            checksum: u8,
+            seen: [u8; 8],
+            len: usize,
         }
 
-        let t = Example::default();
-        let mut b = [0; 34];
-        t.encode_as_me_bytes(&mut b);
-        t.encode_as_be_bytes(&mut b);
-        t.encode_as_le_bytes(&mut b);
+        impl ByteSink for Xor {
+            fn write(&mut self, bytes: &[u8]) {
+                for &byte in bytes {
+                    self.checksum ^= byte;
+                    self.seen[self.len] = byte;
+                    self.len += 1;
+                }
+            }
+        }
+
+        let mut sink = Xor {
+            checksum: 0,
+            seen: [0; 8],
+            len: 0,
+        };
+        let mut frame = [0; 7];
+        let (head, rest) = frame.split_at_mut(1);
+        assert_eq!(1, 0x7Eu8.encode_as_le_bytes_to_sink(head, &mut sink));
+        let (len, body) = rest.split_at_mut(2);
+        assert_eq!(2, 0x0102u16.encode_as_be_bytes_to_sink(len, &mut sink));
+        assert_eq!(
+            4,
+            0x0A0B_0C0Du32.encode_as_le_bytes_to_sink(body, &mut sink)
+        );
+
+        assert_eq!(frame, sink.seen[..sink.len]);
+        assert_eq!(frame.iter().fold(0, |acc, b| acc ^ b), sink.checksum);
     }
 
     #[test]
-    fn derive_all() {
+    fn invert_bytes_field() {
         #[derive(
-            Default, PackedSize, EncodeLE, EncodeBE, EncodeME, DecodeLE, DecodeBE, DecodeME,
+            Debug, Clone, Copy, PartialEq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE,
         )]
-        struct Example {
-            #[endian = "be"]
-            a: u16,
+        struct Line {
+            kind: u8,
+            #[endian_codec(invert_bytes)]
+            word: u16,
         }
 
-        let t = Example::default();
-        let mut b = [0; 2];
-        t.encode_as_me_bytes(&mut b);
-        t.encode_as_be_bytes(&mut b);
-        t.encode_as_le_bytes(&mut b);
+        let line = Line {
+            kind: 0x01,
+            word: 0x0103,
+        };
+        roundtrip!(Line, line, le, [0x01, 0xC0, 0x80]);
+        roundtrip!(Line, line, be, [0x01, 0x80, 0xC0]);
     }
 
     #[test]
-    fn test_codec_2bytes_primitives() {
-        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE)]
-        struct A {
-            a: u16,
-            b: i16,
+    fn decode_from_iter() {
+        #[derive(Debug, PartialEq, PackedSize, DecodeLE, DecodeBE, DecodeME)]
+        #[endian(le)]
+        struct Frame {
+            id: u16,
+            #[endian = "be"]
+            value: u32,
         }
 
-        let test = A { a: 0x2F, b: 0x2F00 };
-        assert_eq!(A::PACKED_LEN, 4);
-        let mut bytes = [0; A::PACKED_LEN];
+        let mut uart = [2, 1, 0, 0, 0, 7, 2, 1, 9].iter().copied();
+        assert_eq!(
+            Ok(Frame {
+                id: 0x0102,
+                value: 7
+            }),
+            Frame::decode_from_me_iter::<6>(uart.by_ref())
+        );
+        assert_eq!(Ok(0x0201), u16::decode_from_be_iter::<2>(uart.by_ref()));
+        assert_eq!(
+            Err(EndianError::BufferTooSmall { needed: 6, got: 1 }),
+            Frame::decode_from_le_iter::<{ Frame::PACKED_LEN }>(uart)
+        );
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            char::decode_from_le_iter::<4>([0, 0xD8, 0, 0].iter().copied())
+        );
 
-        // LE
-        test.encode_as_le_bytes(&mut bytes);
-        assert_eq!([47, 0, 0, 47], bytes);
+        // no size limit
+        let big = <[u8; 2000]>::decode_from_le_iter::<2000>(core::iter::repeat(7));
+        assert_eq!(Ok([7; 2000]), big);
+    }
 
-        let test_back = A::decode_from_le_bytes(&bytes);
-        assert_eq!(test, test_back);
+    #[test]
+    fn non_zero() {
+        let id = NonZeroU32::new(0x0102_0304).unwrap();
+        assert_eq!(4, NonZeroU32::PACKED_LEN);
+        roundtrip!(NonZeroU32, id, le, [4, 3, 2, 1]);
+        roundtrip!(NonZeroU32, id, be, [1, 2, 3, 4]);
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            NonZeroU32::try_decode_from_le_bytes(&[0, 0, 0, 0])
+        );
+        assert_eq!(
+            Ok(NonZeroI8::new(-1).unwrap()),
+            NonZeroI8::try_decode_from_be_bytes(&[0xff])
+        );
 
-        //BE
-        test.encode_as_be_bytes(&mut bytes);
-        assert_eq!([0, 47, 47, 0], bytes);
+        // a field keeps the niche of `Option<NonZeroU32>` and the `Option` keeps its size
+        #[derive(Debug, PartialEq, PackedSize, EncodeLE, DecodeLE)]
+        struct Record {
+            id: NonZeroU32,
+            parent: Option<NonZeroU32>,
+        }
+        assert_eq!(8, Record::PACKED_LEN);
+        roundtrip!(
+            Record,
+            Record { id, parent: None },
+            le,
+            [4, 3, 2, 1, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            Record::try_decode_from_le_bytes(&[0; 8])
+        );
+    }
 
-        let test_back = A::decode_from_be_bytes(&bytes);
-        assert_eq!(test, test_back);
+    #[test]
+    #[should_panic(expected = "zero NonZero value")]
+    fn non_zero_decode_zero() {
+        NonZeroU16::decode_from_le_bytes(&[0, 0]);
     }
 
     #[test]
-    fn test_codec_4bytes_primitives() {
-        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE)]
-        struct A {
-            a: u32,
-            b: i32,
-        }
+    fn non_zero_array() {
+        let handles = [1, 2, 0x0304, 0xffff].map(|handle| NonZeroU16::new(handle).unwrap());
+        assert_eq!(8, <[NonZeroU16; 4]>::PACKED_LEN);
+        roundtrip!([NonZeroU16; 4], handles, le, [1, 0, 2, 0, 4, 3, 0xff, 0xff]);
+        roundtrip!([NonZeroU16; 4], handles, be, [0, 1, 0, 2, 3, 4, 0xff, 0xff]);
 
-        let test = A {
-            a: 0x2F,
-            b: 0x2F000000,
-        };
-        assert_eq!(A::PACKED_LEN, 8);
-        let mut bytes = [0; A::PACKED_LEN];
+        // every element is checked, not only the first one
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            <[NonZeroU16; 4]>::try_decode_from_le_bytes(&[1, 0, 2, 0, 0, 0, 4, 0])
+        );
+    }
 
-        // LE
-        test.encode_as_le_bytes(&mut bytes);
-        assert_eq!([47, 0, 0, 0, 0, 0, 0, 47], bytes);
+    #[test]
+    #[should_panic(expected = "zero NonZero value")]
+    fn non_zero_array_decode_zero() {
+        <[NonZeroU16; 4]>::decode_from_be_bytes(&[0, 1, 0, 2, 0, 3, 0, 0]);
+    }
 
-        let test_back = A::decode_from_le_bytes(&bytes);
-        assert_eq!(test, test_back);
+    #[test]
+    fn skipped_fields() {
+        // type state without any codec implementation
+        #[derive(Debug, PartialEq, Eq)]
+        struct Open;
 
-        //BE
-        test.encode_as_be_bytes(&mut bytes);
-        assert_eq!([0, 0, 0, 47, 47, 0, 0, 0], bytes);
+        #[derive(
+            Debug,
+            PartialEq,
+            Eq,
+            PackedSize,
+            EncodeLE,
+            DecodeLE,
+            EncodeBE,
+            DecodeBE,
+            HashLE,
+            SwapEndian,
+        )]
+        struct Session<S> {
+            id: u16,
+            _state: PhantomData<S>,
+            #[endian(skip)]
+            cached_sum: u32,
+            flags: u8,
+        }
 
-        let test_back = A::decode_from_be_bytes(&bytes);
-        assert_eq!(test, test_back);
+        assert_eq!(3, Session::<Open>::PACKED_LEN);
+        let session = Session::<Open> {
+            id: 0x0102,
+            _state: PhantomData,
+            cached_sum: 7,
+            flags: 3,
+        };
+        let mut bytes = [0; 3];
+        session.encode_as_le_bytes(&mut bytes);
+        assert_eq!([2, 1, 3], bytes);
+        let decoded = Session::<Open>::decode_from_le_bytes(&bytes);
+        assert_eq!(0, decoded.cached_sum);
+        assert_eq!(
+            Session {
+                cached_sum: 7,
+                ..decoded
+            },
+            session
+        );
+        Session::<Open>::swap_endian_in_buffer(&mut bytes);
+        assert_eq!([1, 2, 3], bytes);
     }
 
     #[test]
-    fn test_codec_8bytes_primitives() {
-        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE)]
-        struct A {
-            a: u64,
-            b: i64,
+    fn debug_bytes() {
+        #[derive(PackedSize, EncodeLE)]
+        #[endian_codec(debug_bytes)]
+        struct Header {
+            kind: u8,
+            len: u16,
         }
 
-        let test = A {
-            a: 0x2F,
-            b: 0x2F000000_00000000,
+        #[derive(PackedSize, EncodeLE)]
+        #[endian_codec(debug_bytes)]
+        struct Id(u16);
+
+        let header = Header {
+            kind: 1,
+            len: 0x0203,
         };
-        assert_eq!(A::PACKED_LEN, 16);
-        let mut bytes = [0; A::PACKED_LEN];
+        assert_eq!(
+            "Header { kind: 1, len: 515, le_bytes: 010302 }",
+            std::format!("{:?}", header)
+        );
+        assert_eq!("Id(4660, 3412)", std::format!("{:?}", Id(0x1234)));
+    }
 
-        // LE
-        test.encode_as_le_bytes(&mut bytes);
-        assert_eq!([47, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 47], bytes);
+    #[test]
+    fn decode_trailing_into_scratch() {
+        #[derive(Debug, Clone, Copy, Default, PartialEq, PackedSize, DecodeLE)]
+        struct Point {
+            x: i16,
+            y: i16,
+        }
 
-        let test_back = A::decode_from_le_bytes(&bytes);
-        assert_eq!(test, test_back);
+        let mut pool = [Point::default(); 3];
+        let mut scratch = Scratch::new(&mut pool);
 
-        //BE
-        test.encode_as_be_bytes(&mut bytes);
-        assert_eq!([0, 0, 0, 0, 0, 0, 0, 47, 47, 0, 0, 0, 0, 0, 0, 0,], bytes);
+        let frame = [2, 1, 0, 2, 0, 3, 0, 4, 0];
+        let (count, points) = decode_trailing_le::<u8, Point>(&frame).unwrap();
+        let first = points.collect_into(&mut scratch).unwrap();
+        assert_eq!(2, count);
 
-        let test_back = A::decode_from_be_bytes(&bytes);
-        assert_eq!(test, test_back);
+        let frame = [2, 5, 0, 6, 0, 7, 0, 8, 0];
+        let (_, points) = decode_trailing_le::<u8, Point>(&frame).unwrap();
+        assert_eq!(
+            Err(EndianError::ScratchTooSmall { needed: 2, got: 1 }),
+            points.collect_into(&mut scratch)
+        );
+
+        let (_, points) = decode_trailing_le::<u8, Point>(&frame[..5]).unwrap();
+        let second = points.collect_into(&mut scratch).unwrap();
+        assert_eq!([Point { x: 1, y: 2 }, Point { x: 3, y: 4 }], *first);
+        assert_eq!([Point { x: 5, y: 6 }], *second);
+        assert_eq!(0, scratch.remaining());
     }
 
     #[test]
-    fn test_codec_16bytes_primitives() {
-        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE)]
-        struct A {
-            a: u128,
-            b: i128,
+    fn pdp_middle_endian() {
+        #[derive(Debug, PartialEq, PackedSize, EncodeME, DecodeME)]
+        struct Register {
+            #[endian = "pdp"]
+            address: u32,
+            #[endian = "middle"]
+            offset: i32,
+            #[endian = "le"]
+            flags: u16,
         }
 
-        let test = A {
-            a: 0x2F,
-            b: 0x2F000000_00000000_00000000_00000000,
-        };
-        assert_eq!(A::PACKED_LEN, 32);
-        let mut bytes = [0; A::PACKED_LEN];
+        roundtrip!(
+            Register,
+            Register {
+                address: 0x0A0B_0C0D,
+                offset: -2,
+                flags: 0x0102,
+            },
+            me,
+            [0x0B, 0x0A, 0x0D, 0x0C, 0xff, 0xff, 0xfe, 0xff, 0x02, 0x01]
+        );
+    }
+
+    #[test]
+    fn try_decode() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE, DecodeBE, DecodeME)]
+        #[endian(le)]
+        struct Example {
+            a: u16,
+            b: u8,
+        }
 
-        // LE
-        test.encode_as_le_bytes(&mut bytes);
         assert_eq!(
-            [
-                47, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-                0, 0, 0, 0, 47
-            ],
-            bytes
+            Ok(Example { a: 0x0102, b: 3 }),
+            Example::try_decode_from_le_bytes(&[2, 1, 3])
+        );
+        assert_eq!(
+            Ok(Example { a: 0x0102, b: 3 }),
+            Example::try_decode_from_be_bytes(&[1, 2, 3])
+        );
+        assert_eq!(
+            Err(EndianError::BufferTooSmall { needed: 3, got: 2 }),
+            Example::try_decode_from_me_bytes(&[2, 1])
         );
+        assert_eq!(
+            Err(EndianError::BufferTooLarge { needed: 3, got: 4 }),
+            Example::try_decode_from_le_bytes(&[0; 4])
+        );
+    }
 
-        let test_back = A::decode_from_le_bytes(&bytes);
-        assert_eq!(test, test_back);
+    #[test]
+    fn try_decode_invalid_field() {
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE, DecodeBE)]
+        enum Kind {
+            Ping,
+            Data,
+        }
 
-        //BE
-        test.encode_as_be_bytes(&mut bytes);
+        #[derive(Debug, PartialEq, Eq, PackedSize, DecodeLE, DecodeBE)]
+        struct Packet {
+            kind: Kind,
+            len: u8,
+            #[endian_codec(hex)]
+            id: u8,
+            tag: (char, NonZeroU16),
+        }
+
+        let valid = [1, 2, b'f', b'f', 0x41, 0, 0, 0, 1, 0];
         assert_eq!(
-            [
-                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 47, 47, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-                0, 0, 0, 0, 0,
+            Ok(Packet {
+                kind: Kind::Data,
+                len: 2,
+                id: 0xff,
+                tag: ('A', NonZeroU16::new(1).unwrap()),
+            }),
+            Packet::try_decode_from_le_bytes(&valid)
+        );
+
+        let mut invalid_kind = valid;
+        invalid_kind[0] = 5;
+        let mut invalid_hex = valid;
+        invalid_hex[2] = b'g';
+        let mut invalid_char = valid;
+        invalid_char[5] = 0xD8;
+        let mut zero = valid;
+        zero[8] = 0;
+        for bytes in [invalid_kind, invalid_hex, invalid_char, zero].iter() {
+            assert_eq!(
+                Err(EndianError::InvalidValue),
+                Packet::try_decode_from_le_bytes(bytes)
+            );
+        }
+        assert_eq!(
+            Err(EndianError::InvalidValue),
+            Packet::try_decode_from_be_bytes(&invalid_kind)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_le_bytes() {
+        use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_tokens, Token};
+
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, SerdeLE)]
+        struct Version {
+            major: u16,
+            minor: u8,
+        }
+
+        let version = Version { major: 1, minor: 2 };
+        assert_tokens(&version, &[Token::Bytes(&[1, 0, 2])]);
+        assert_de_tokens(
+            &version,
+            &[
+                Token::Seq { len: Some(3) },
+                Token::U8(1),
+                Token::U8(0),
+                Token::U8(2),
+                Token::SeqEnd,
             ],
-            bytes
+        );
+        assert_de_tokens_error::<Version>(
+            &[Token::Bytes(&[1, 0])],
+            "invalid length 2, expected 3 little-endian bytes",
         );
 
-        let test_back = A::decode_from_be_bytes(&bytes);
-        assert_eq!(test, test_back);
+        #[derive(Debug, PartialEq, Eq, PackedSize, EncodeLE, DecodeLE, SerdeLE)]
+        struct Letter {
+            value: char,
+        }
+
+        assert_de_tokens_error::<Letter>(
+            &[Token::Bytes(&[0, 0xd8, 0, 0])],
+            "bytes hold an invalid value",
+        );
     }
 
     /*
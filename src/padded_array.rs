@@ -0,0 +1,187 @@
+use crate::{
+    check_decode_len, hash_zeros, DecodeBE, DecodeLE, DecodeME, DecodeNE, EncodeBE, EncodeLE,
+    EncodeME, EncodeNE, EndianError, HashLE, PackedSize,
+};
+use core::hash::Hasher;
+
+/// Up to `CAP` elements packed as a `u16` count followed by `CAP` slots.
+///
+/// Slots after the used elements are padded with zero bytes, so `PACKED_LEN` doesn't depend on
+/// the number of elements. The mixed-endian encoding stores the count as a little-endian and
+/// every element with its own mixed-endian layout.
+///
+/// ```rust
+/// use endian_codec::{EncodeLE, PackedSize, PaddedArray};
+///
+/// let users = PaddedArray::<u16, 3>::from_slice(&[7, 9]).unwrap();
+/// let mut buf = [0xff; PaddedArray::<u16, 3>::PACKED_LEN];
+/// users.encode_as_le_bytes(&mut buf);
+/// assert_eq!([2, 0, 7, 0, 9, 0, 0, 0], buf);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PaddedArray<T, const CAP: usize> {
+    // slots from `len` onward always hold `T::default()`
+    items: [T; CAP],
+    len: usize,
+}
+
+impl<T: Default, const CAP: usize> PaddedArray<T, CAP> {
+    /// Create an empty array.
+    ///
+    /// # Panics
+    ///
+    /// Panic if `CAP` doesn't fit in the `u16` count.
+    pub fn new() -> Self {
+        assert!(CAP <= u16::MAX as usize, "PaddedArray capacity exceeds u16");
+        Self {
+            items: core::array::from_fn(|_| T::default()),
+            len: 0,
+        }
+    }
+
+    /// Copy `items` into a new array, return `None` if there are more than `CAP` of them.
+    pub fn from_slice(items: &[T]) -> Option<Self>
+    where
+        T: Clone,
+    {
+        if items.len() > CAP {
+            return None;
+        }
+        let mut array = Self::new();
+        array.items[..items.len()].clone_from_slice(items);
+        array.len = items.len();
+        Some(array)
+    }
+
+    /// Append `item`, or give it back if the array is full.
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        if self.len == CAP {
+            return Err(item);
+        }
+        self.items[self.len] = item;
+        self.len += 1;
+        Ok(())
+    }
+}
+
+impl<T, const CAP: usize> PaddedArray<T, CAP> {
+    /// Used elements.
+    pub fn as_slice(&self) -> &[T] {
+        &self.items[..self.len]
+    }
+
+    /// Number of used elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return `true` if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T: Default, const CAP: usize> Default for PaddedArray<T, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PackedSize, const CAP: usize> PackedSize for PaddedArray<T, CAP> {
+    const PACKED_LEN: usize = u16::PACKED_LEN + CAP * T::PACKED_LEN;
+}
+
+macro_rules! impl_codec_for_padded_array {
+    ($encode:ident, $encode_fn:ident, $decode:ident, $decode_fn:ident, $try_decode_fn:ident;
+     count: $count_encode_fn:ident, $count_decode_fn:ident) => {
+        impl<T: $encode, const CAP: usize> $encode for PaddedArray<T, CAP> {
+            fn $encode_fn(&self, bytes: &mut [u8]) -> usize {
+                let (count, slots) = bytes.split_at_mut(u16::PACKED_LEN);
+                (self.len as u16).$count_encode_fn(count);
+                let (used, padding) = slots.split_at_mut(self.len * T::PACKED_LEN);
+                for (item, slot) in self
+                    .as_slice()
+                    .iter()
+                    .zip(used.chunks_exact_mut(T::PACKED_LEN))
+                {
+                    item.$encode_fn(slot);
+                }
+                padding.iter_mut().for_each(|b| *b = 0);
+                Self::PACKED_LEN
+            }
+        }
+
+        impl<T: $decode + Default, const CAP: usize> $decode for PaddedArray<T, CAP> {
+            /// # Panics
+            ///
+            /// Panic if the count is bigger than `CAP`.
+            fn $decode_fn(bytes: &[u8]) -> Self {
+                let (count, slots) = bytes.split_at(u16::PACKED_LEN);
+                let len = usize::from(u16::$count_decode_fn(count));
+                assert!(
+                    len <= CAP,
+                    "PaddedArray count {} exceeds capacity {}",
+                    len,
+                    CAP
+                );
+                let mut array = Self::new();
+                for (item, slot) in array.items[..len]
+                    .iter_mut()
+                    .zip(slots.chunks_exact(T::PACKED_LEN))
+                {
+                    *item = T::$decode_fn(slot);
+                }
+                array.len = len;
+                array
+            }
+
+            /// Return [InvalidValue](EndianError::InvalidValue) if the count is bigger than
+            /// `CAP` or an element is invalid.
+            fn $try_decode_fn(bytes: &[u8]) -> Result<Self, EndianError> {
+                check_decode_len(bytes.len(), Self::PACKED_LEN)?;
+                let (count, slots) = bytes.split_at(u16::PACKED_LEN);
+                let len = usize::from(u16::$count_decode_fn(count));
+                if len > CAP {
+                    return Err(EndianError::InvalidValue);
+                }
+                let mut array = Self::new();
+                for (item, slot) in array.items[..len]
+                    .iter_mut()
+                    .zip(slots.chunks_exact(T::PACKED_LEN))
+                {
+                    *item = T::$try_decode_fn(slot)?;
+                }
+                array.len = len;
+                Ok(array)
+            }
+        }
+    };
+}
+
+impl_codec_for_padded_array!(
+    EncodeLE, encode_as_le_bytes, DecodeLE, decode_from_le_bytes, try_decode_from_le_bytes;
+    count: encode_as_le_bytes, decode_from_le_bytes
+);
+impl_codec_for_padded_array!(
+    EncodeBE, encode_as_be_bytes, DecodeBE, decode_from_be_bytes, try_decode_from_be_bytes;
+    count: encode_as_be_bytes, decode_from_be_bytes
+);
+// `u16` has no mixed-endian layout, the count is stored as a little-endian.
+impl_codec_for_padded_array!(
+    EncodeME, encode_as_me_bytes, DecodeME, decode_from_me_bytes, try_decode_from_me_bytes;
+    count: encode_as_le_bytes, decode_from_le_bytes
+);
+impl_codec_for_padded_array!(
+    EncodeNE, encode_as_ne_bytes, DecodeNE, decode_from_ne_bytes, try_decode_from_ne_bytes;
+    count: encode_as_ne_bytes, decode_from_ne_bytes
+);
+
+impl<T: HashLE + PackedSize, const CAP: usize> HashLE for PaddedArray<T, CAP> {
+    fn hash_le<H: Hasher>(&self, state: &mut H) {
+        (self.len as u16).hash_le(state);
+        for item in self.as_slice() {
+            item.hash_le(state);
+        }
+        hash_zeros(state, (CAP - self.len) * T::PACKED_LEN);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::PackedSize;
+use core::mem::{align_of, size_of};
+
+/// Types whose memory layout is exactly their native-endian encoding.
+///
+/// Such types can be decoded by [decode_ne_pod](decode_ne_pod), which copies the bytes straight
+/// into the value and skips the field-wise work of [DecodeNE](crate::DecodeNE).
+///
+/// ```rust
+/// use endian_codec::{decode_ne_pod, DecodeNE, PackedSize, Pod};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, PackedSize, DecodeNE)]
+/// #[repr(C)]
+/// struct Sample {
+///   time: u32,
+///   channels: [i16; 2],
+/// }
+///
+/// // SAFETY: `repr(C)` without padding, fields in wire order and `Pod` themselves.
+/// unsafe impl Pod for Sample {}
+///
+/// let bytes = 7u32.to_ne_bytes();
+/// let bytes = [bytes[0], bytes[1], bytes[2], bytes[3], 1, 0, 0, 1];
+/// assert_eq!(Sample::decode_from_ne_bytes(&bytes), decode_ne_pod::<Sample>(&bytes));
+/// ```
+///
+/// # Safety
+///
+/// Implement only for types which:
+/// - are valid for any bit pattern (so no `bool`, `char`, references or enums),
+/// - have no padding, i.e. `size_of::<Self>()` equals `PACKED_LEN`,
+/// - are `#[repr(C)]` (or `#[repr(transparent)]`) with fields declared in wire order, each of them
+///   `Pod` itself and without `#[endian_codec(..)]` attributes changing the encoding.
+///
+/// Decoding panics if the size doesn't match, the rest is up to the implementation.
+pub unsafe trait Pod: PackedSize + Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($type:ty),+) => {
+        $(
+            // SAFETY: integers and floats are valid for any bit pattern.
+            unsafe impl Pod for $type {}
+        )+
+    };
+}
+
+impl_pod!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, f32, f64);
+
+// SAFETY: arrays have no padding between elements.
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+fn assert_no_padding<T: Pod>() {
+    assert!(
+        size_of::<T>() == T::PACKED_LEN,
+        "Pod type has padding, its size differs from PACKED_LEN"
+    );
+}
+
+/// Decode `T` from native-endian `bytes` by copying them into the value.
+///
+/// Gives the same value as [DecodeNE](crate::DecodeNE) without decoding field by field. `bytes`
+/// don't need to be aligned.
+///
+/// # Panics
+///
+/// Panic if [PackedSize](PackedSize) represents a different size than `bytes` slice or than `T`
+/// takes in memory.
+pub fn decode_ne_pod<T: Pod>(bytes: &[u8]) -> T {
+    assert_no_padding::<T>();
+    assert_eq!(T::PACKED_LEN, bytes.len());
+    // SAFETY: `bytes` cover the whole `T` which is valid for any bit pattern.
+    unsafe { bytes.as_ptr().cast::<T>().read_unaligned() }
+}
+
+/// View native-endian `bytes` as `T` without copying.
+///
+/// Return `None` if `bytes` aren't aligned for `T` or their length differs from `PACKED_LEN`.
+///
+/// # Panics
+///
+/// Panic if `T` takes a different size in memory than [PackedSize](PackedSize) represents.
+pub fn cast_ne_pod<T: Pod>(bytes: &[u8]) -> Option<&T> {
+    assert_no_padding::<T>();
+    if bytes.len() != T::PACKED_LEN || bytes.as_ptr().align_offset(align_of::<T>()) != 0 {
+        return None;
+    }
+    // SAFETY: `bytes` are aligned, cover the whole `T` and `T` is valid for any bit pattern.
+    Some(unsafe { &*bytes.as_ptr().cast::<T>() })
+}
@@ -0,0 +1,25 @@
+//! Common traits and derives in a single import.
+//!
+//! ```rust
+//! use endian_codec::prelude::*;
+//!
+//! #[derive(Debug, PartialEq, PackedSize, EncodeLE, DecodeLE)]
+//! struct Version {
+//!   major: u16,
+//!   minor: u16,
+//! }
+//!
+//! let mut bytes = [0; Version::PACKED_LEN];
+//! Version { major: 1, minor: 2 }.encode_as_le_bytes(&mut bytes);
+//! assert_eq!(Version { major: 1, minor: 2 }, Version::decode_from_le_bytes(&bytes));
+//! ```
+
+// Traits which have a derive of the same name bring the derive too.
+pub use crate::{
+    ByteOrder, ByteSink, CodecBE, CodecLE, CodecME, DebugLayout, Decode, DecodeBE, DecodeLE,
+    DecodeLEVersioned, DecodeME, DecodeNE, DecodeWithEndian, Encode, EncodeBE, EncodeLE, EncodeME,
+    EncodeNE, HashLE, PackedSize, Pod, SwapEndian,
+};
+
+#[cfg(feature = "endian_codec_derive")]
+pub use crate::{DecodeLEHybrid, EndianNewtypes, SerdeLE};
@@ -0,0 +1,46 @@
+/// Bump allocator handing out consecutive parts of a caller-provided buffer.
+///
+/// Decoded variable-length data can be placed into it with
+/// [DecodedIter::collect_into](crate::DecodedIter::collect_into), so a pool (e.g. a `static`
+/// array) can be used instead of the global allocator. Taken slots are released only when the
+/// `Scratch` and the slices it handed out are dropped.
+///
+/// ```rust
+/// use endian_codec::Scratch;
+///
+/// let mut pool = [0u16; 8];
+/// let mut scratch = Scratch::new(&mut pool);
+/// let frame = [2, 0, 10, 0, 20, 0];
+/// let (count, records) = endian_codec::decode_trailing_le::<u16, u16>(&frame)?;
+/// let records = records.collect_into(&mut scratch)?;
+/// assert_eq!(2, count);
+/// assert_eq!([10, 20], *records);
+/// assert_eq!(6, scratch.remaining());
+/// # Ok::<(), endian_codec::EndianError>(())
+/// ```
+#[derive(Debug)]
+pub struct Scratch<'a, T> {
+    free: &'a mut [T],
+}
+
+impl<'a, T> Scratch<'a, T> {
+    /// Hand out slots of `buffer`, starting from its beginning.
+    pub fn new(buffer: &'a mut [T]) -> Self {
+        Scratch { free: buffer }
+    }
+
+    /// Number of slots which weren't handed out yet.
+    pub fn remaining(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Take the next `len` slots. Return `None` if fewer than `len` slots remain.
+    pub fn alloc(&mut self, len: usize) -> Option<&'a mut [T]> {
+        if len > self.free.len() {
+            return None;
+        }
+        let (taken, free) = core::mem::take(&mut self.free).split_at_mut(len);
+        self.free = free;
+        Some(taken)
+    }
+}
@@ -0,0 +1,57 @@
+//! Support for the `SerdeLE` derive, enabled with the `serde` feature.
+
+use crate::{DecodeLE, EncodeLE};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::Serializer;
+
+pub use serde;
+
+/// Serialize `value` as its little-endian bytes.
+pub fn serialize_le<T: EncodeLE, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut bytes = vec![0; T::PACKED_LEN];
+    value.encode_as_le_bytes(&mut bytes);
+    serializer.serialize_bytes(&bytes)
+}
+
+/// Deserialize `T` from its little-endian bytes.
+pub fn deserialize_le<'de, T: DecodeLE, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    deserializer.deserialize_bytes(LeBytesVisitor(PhantomData))
+}
+
+struct LeBytesVisitor<T>(PhantomData<T>);
+
+impl<'de, T: DecodeLE> Visitor<'de> for LeBytesVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} little-endian bytes", T::PACKED_LEN)
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<T, E> {
+        if bytes.len() != T::PACKED_LEN {
+            return Err(E::invalid_length(bytes.len(), &self));
+        }
+        T::try_decode_from_le_bytes(bytes).map_err(E::custom)
+    }
+
+    // formats without a bytes type store them as a sequence
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        let mut bytes = Vec::with_capacity(T::PACKED_LEN);
+        while let Some(byte) = seq.next_element()? {
+            if bytes.len() == T::PACKED_LEN {
+                return Err(de::Error::invalid_length(bytes.len() + 1, &self));
+            }
+            bytes.push(byte);
+        }
+        self.visit_bytes(&bytes)
+    }
+}
@@ -0,0 +1,11 @@
+use endian_codec::PackedSize;
+
+#[derive(PackedSize)]
+#[endian_codec(assert_field_order("major", "minor", "patch"))]
+struct Version {
+    major: u16,
+    patch: u16,
+    minor: u16,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use endian_codec::PackedSize;
+
+#[derive(PackedSize)]
+#[endian_codec(assert_packed_len = 4)]
+struct Version {
+    major: u16,
+    minor: u16,
+    patch: u16,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use endian_codec::{EncodeLE, PackedSize};
+
+#[derive(PackedSize, EncodeLE)]
+#[endian_codec(debug_bytes)]
+enum Kind {
+    A,
+    B,
+}
+
+fn main() {}
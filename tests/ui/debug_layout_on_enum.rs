@@ -0,0 +1,9 @@
+use endian_codec::{DebugLayout, PackedSize};
+
+#[derive(PackedSize, DebugLayout)]
+enum Kind {
+    A,
+    B,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use endian_codec::{EncodeLE, PackedSize};
+
+#[derive(PackedSize, EncodeLE)]
+union Word {
+    raw: u32,
+    halves: [u16; 2],
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use core::marker::PhantomData;
+use endian_codec::{EncodeLE, EncodeME, PackedSize};
+
+#[derive(PackedSize, EncodeME)]
+struct Example {
+    #[endian = "le"]
+    a: u16,
+    #[endian = "be"]
+    _m: PhantomData<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use endian_codec::{DecodeLE, DecodeLEHybrid, PackedSize};
+
+#[derive(PackedSize, DecodeLEHybrid)]
+struct Packet {
+    kind: u16,
+    #[borrow]
+    payload: [u8; 4],
+}
+
+fn main() {
+    let bytes = vec![1, 0, 2, 3, 4, 5];
+    let packet = PacketHybrid::decode_from_le_bytes(&bytes);
+    drop(bytes);
+    assert_eq!(&[2, 3, 4, 5], packet.payload);
+}
@@ -0,0 +1,6 @@
+use endian_codec::{DecodeLEHybrid, PackedSize};
+
+#[derive(PackedSize, DecodeLEHybrid)]
+struct Packet(u16, #[borrow] [u8; 4]);
+
+fn main() {}
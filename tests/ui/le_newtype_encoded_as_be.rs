@@ -0,0 +1,11 @@
+use endian_codec::{DecodeBE, DecodeLE, EncodeBE, EncodeLE, EndianNewtypes, PackedSize};
+
+#[derive(PackedSize, EncodeLE, DecodeLE, EncodeBE, DecodeBE, EndianNewtypes)]
+struct Frame {
+    a: u16,
+}
+
+fn main() {
+    let mut bytes = [0; Frame::PACKED_LEN];
+    LeFrame(Frame { a: 1 }).encode_as_be_bytes(&mut bytes);
+}
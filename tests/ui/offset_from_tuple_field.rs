@@ -0,0 +1,6 @@
+use endian_codec::PackedSize;
+
+#[derive(PackedSize)]
+struct Header(u16, #[endian_codec(offset_from = "0")] u16);
+
+fn main() {}
@@ -0,0 +1,21 @@
+use endian_codec::{DecodeLE, DecodeLEHybrid, PackedSize};
+
+#[derive(PackedSize, DecodeLEHybrid)]
+struct Packet {
+    kind: u16,
+    #[borrow]
+    payload: [u8; 4],
+}
+
+fn payload(bytes: &[u8]) -> &[u8; 4] {
+    // the view may return borrows which live as long as the buffer
+    PacketHybrid::decode_from_le_bytes(bytes).payload
+}
+
+fn main() {
+    let bytes = vec![1, 0, 2, 3, 4, 5];
+    let packet = PacketHybrid::decode_from_le_bytes(&bytes);
+    assert_eq!(1, packet.kind);
+    assert_eq!(&[2, 3, 4, 5], packet.payload);
+    assert_eq!(&[2, 3, 4, 5], payload(&bytes));
+}
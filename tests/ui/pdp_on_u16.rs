@@ -0,0 +1,11 @@
+use endian_codec::{EncodeME, PackedSize};
+
+#[derive(PackedSize, EncodeME)]
+struct Example {
+    #[endian = "pdp"]
+    word: u32,
+    #[endian = "pdp"]
+    half: u16,
+}
+
+fn main() {}
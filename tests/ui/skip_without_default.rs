@@ -0,0 +1,12 @@
+use endian_codec::{DecodeLE, PackedSize};
+
+struct Cache;
+
+#[derive(PackedSize, DecodeLE)]
+struct Example {
+    a: u16,
+    #[endian(skip)]
+    cache: Cache,
+}
+
+fn main() {}